@@ -63,6 +63,13 @@ pub use self::rx::*;
 /// We use the same limit to compute the view sent to peers locally.
 pub(crate) const MAX_VIEW_HEADS: usize = 5;
 
+/// The only protocol version the bridge encodes and decodes messages of.
+///
+/// Peers which negotiate another version of a peer set, e.g. a main version 2 configured in
+/// [`PeerSetProtocolNames`](polkadot_node_network_protocol::peer_set::PeerSetProtocolNames),
+/// are disconnected.
+pub(crate) const SUPPORTED_PROTOCOL_VERSION: ProtocolVersion = 1;
+
 pub(crate) const MALFORMED_MESSAGE_COST: Rep = Rep::CostMajor("Malformed Network-bridge message");
 pub(crate) const UNCONNECTED_PEERSET_COST: Rep =
 	Rep::CostMinor("Message sent to un-connected peer-set");
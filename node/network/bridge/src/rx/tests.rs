@@ -68,6 +68,7 @@ pub enum NetworkAction {
 struct TestNetwork {
 	net_events: Arc<Mutex<Option<SingleItemStream<NetworkEvent>>>>,
 	action_tx: Arc<Mutex<metered::UnboundedMeteredSender<NetworkAction>>>,
+	peerset_protocol_names: Arc<PeerSetProtocolNames>,
 }
 
 #[derive(Clone, Debug)]
@@ -78,9 +79,12 @@ struct TestAuthorityDiscovery;
 struct TestNetworkHandle {
 	action_rx: metered::UnboundedMeteredReceiver<NetworkAction>,
 	net_tx: SingleItemSink<NetworkEvent>,
+	peerset_protocol_names: PeerSetProtocolNames,
 }
 
-fn new_test_network() -> (TestNetwork, TestNetworkHandle, TestAuthorityDiscovery) {
+fn new_test_network(
+	peerset_protocol_names: PeerSetProtocolNames,
+) -> (TestNetwork, TestNetworkHandle, TestAuthorityDiscovery) {
 	let (net_tx, net_rx) = polkadot_node_subsystem_test_helpers::single_item_sink();
 	let (action_tx, action_rx) = metered::unbounded();
 
@@ -88,8 +92,9 @@ fn new_test_network() -> (TestNetwork, TestNetworkHandle, TestAuthorityDiscovery
 		TestNetwork {
 			net_events: Arc::new(Mutex::new(Some(net_rx))),
 			action_tx: Arc::new(Mutex::new(action_tx)),
+			peerset_protocol_names: Arc::new(peerset_protocol_names.clone()),
 		},
-		TestNetworkHandle { action_rx, net_tx },
+		TestNetworkHandle { action_rx, net_tx, peerset_protocol_names },
 		TestAuthorityDiscovery,
 	)
 }
@@ -130,14 +135,18 @@ impl Network for TestNetwork {
 			.unwrap();
 	}
 
-	fn disconnect_peer(&self, who: PeerId, peer_set: PeerSet) {
+	fn disconnect_peer(&self, who: PeerId, protocol: Cow<'static, str>) {
+		let (peer_set, _) = self.peerset_protocol_names.try_get_protocol(&protocol).unwrap();
+
 		self.action_tx
 			.lock()
 			.unbounded_send(NetworkAction::DisconnectPeer(who, peer_set))
 			.unwrap();
 	}
 
-	fn write_notification(&self, who: PeerId, peer_set: PeerSet, message: Vec<u8>) {
+	fn write_notification(&self, who: PeerId, protocol: Cow<'static, str>, message: Vec<u8>) {
+		let (peer_set, _) = self.peerset_protocol_names.try_get_protocol(&protocol).unwrap();
+
 		self.action_tx
 			.lock()
 			.unbounded_send(NetworkAction::WriteNotification(who, peer_set, message))
@@ -181,7 +190,7 @@ impl TestNetworkHandle {
 	async fn connect_peer(&mut self, peer: PeerId, peer_set: PeerSet, role: ObservedRole) {
 		self.send_network_event(NetworkEvent::NotificationStreamOpened {
 			remote: peer,
-			protocol: peer_set.into_default_protocol_name(),
+			protocol: self.peerset_protocol_names.get_main_name(peer_set),
 			negotiated_fallback: None,
 			role: role.into(),
 		})
@@ -191,7 +200,7 @@ impl TestNetworkHandle {
 	async fn disconnect_peer(&mut self, peer: PeerId, peer_set: PeerSet) {
 		self.send_network_event(NetworkEvent::NotificationStreamClosed {
 			remote: peer,
-			protocol: peer_set.into_default_protocol_name(),
+			protocol: self.peerset_protocol_names.get_main_name(peer_set),
 		})
 		.await;
 	}
@@ -199,7 +208,7 @@ impl TestNetworkHandle {
 	async fn peer_message(&mut self, peer: PeerId, peer_set: PeerSet, message: Vec<u8>) {
 		self.send_network_event(NetworkEvent::NotificationsReceived {
 			remote: peer,
-			messages: vec![(peer_set.into_default_protocol_name(), message.into())],
+			messages: vec![(self.peerset_protocol_names.get_main_name(peer_set), message.into())],
 		})
 		.await;
 	}
@@ -284,9 +293,21 @@ struct TestHarness {
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	sync_oracle: Box<dyn SyncOracle + Send>,
 	test: impl FnOnce(TestHarness) -> T,
+) {
+	let genesis_hash = Hash::repeat_byte(0xff);
+	let fork_id = None;
+	let peerset_protocol_names = PeerSetProtocolNames::new(genesis_hash, fork_id);
+
+	test_harness_with_protocol_names(sync_oracle, peerset_protocol_names, test)
+}
+
+fn test_harness_with_protocol_names<T: Future<Output = VirtualOverseer>>(
+	sync_oracle: Box<dyn SyncOracle + Send>,
+	peerset_protocol_names: PeerSetProtocolNames,
+	test: impl FnOnce(TestHarness) -> T,
 ) {
 	let pool = sp_core::testing::TaskExecutor::new();
-	let (mut network, network_handle, discovery) = new_test_network();
+	let (mut network, network_handle, discovery) = new_test_network(peerset_protocol_names.clone());
 	let (context, virtual_overseer) =
 		polkadot_node_subsystem_test_helpers::make_subsystem_context(pool);
 	let network_stream = network.event_stream();
@@ -297,6 +318,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 		metrics: Metrics(None),
 		sync_oracle,
 		shared: Shared::default(),
+		peerset_protocol_names,
 	};
 
 	let network_bridge = run_network_in(bridge, context, network_stream)
@@ -928,6 +950,62 @@ fn relays_collation_protocol_messages() {
 	});
 }
 
+#[test]
+fn peers_on_unsupported_versions_are_disconnected() {
+	let peerset_protocol_names = PeerSetProtocolNames::builder()
+		.genesis(Hash::repeat_byte(0xff))
+		.extra_version(PeerSet::Collation, 2)
+		.main_version(PeerSet::Collation, 2)
+		.build()
+		.unwrap();
+	let v1_name = peerset_protocol_names.get_name(PeerSet::Collation, 1);
+
+	test_harness_with_protocol_names(
+		done_syncing_oracle(),
+		peerset_protocol_names,
+		|test_harness| async move {
+			let TestHarness { mut network_handle, mut virtual_overseer } = test_harness;
+
+			let peer_a = PeerId::random();
+			let peer_b = PeerId::random();
+
+			// peer A negotiates the main version 2, which the bridge can't speak.
+			network_handle
+				.connect_peer(peer_a.clone(), PeerSet::Collation, ObservedRole::Full)
+				.await;
+			assert_eq!(
+				network_handle.next_network_action().await,
+				NetworkAction::DisconnectPeer(peer_a.clone(), PeerSet::Collation),
+			);
+
+			// peer B falls back to version 1.
+			let main_name = network_handle.peerset_protocol_names.get_main_name(PeerSet::Collation);
+			network_handle
+				.send_network_event(NetworkEvent::NotificationStreamOpened {
+					remote: peer_b.clone(),
+					protocol: main_name,
+					negotiated_fallback: Some(v1_name),
+					role: ObservedRole::Full.into(),
+				})
+				.await;
+
+			// only peer B is reported as connected.
+			assert_sends_collation_event_to_all(
+				NetworkBridgeEvent::PeerConnected(peer_b.clone(), ObservedRole::Full, 1, None),
+				&mut virtual_overseer,
+			)
+			.await;
+			assert_sends_collation_event_to_all(
+				NetworkBridgeEvent::PeerViewChange(peer_b.clone(), View::default()),
+				&mut virtual_overseer,
+			)
+			.await;
+
+			virtual_overseer
+		},
+	);
+}
+
 #[test]
 fn different_views_on_different_peer_sets() {
 	test_harness(done_syncing_oracle(), |test_harness| async move {
@@ -16,11 +16,17 @@
 
 //! All peersets and protocols used for parachains.
 
-use super::ProtocolVersion;
-use sc_network::config::{NonDefaultSetConfig, SetConfig};
+use super::{
+	request_response::{Protocol, ReqProtocolNames},
+	ProtocolVersion, UnifiedReputationChange as Rep,
+};
+use polkadot_primitives::v2::Hash;
+use sc_network::config::{NetworkConfiguration, NonDefaultSetConfig, ProtocolId, SetConfig};
 use std::{
 	borrow::Cow,
-	ops::{Index, IndexMut},
+	collections::{hash_map::Entry, HashMap, HashSet},
+	ops::{Index, IndexMut, RangeInclusive},
+	time::Duration,
 };
 use strum::{EnumIter, IntoEnumIterator};
 
@@ -34,6 +40,36 @@ pub const DEFAULT_VALIDATION_PROTOCOL_VERSION: ProtocolVersion = 1;
 /// The default collation protocol version.
 pub const DEFAULT_COLLATION_PROTOCOL_VERSION: ProtocolVersion = 1;
 
+/// Protocol names longer than this are never looked up, as none of ours is anywhere close.
+pub const MAX_PROTOCOL_NAME_LEN: usize = 256;
+
+const LOG_TARGET: &str = "parachain::peer-set";
+
+/// The default number of inbound collation slots of authorities.
+pub const DEFAULT_COLLATION_AUTHORITY_IN_PEERS: u32 = 100;
+
+/// The default maximum size of a notification, in bytes.
+pub const DEFAULT_MAX_NOTIFICATION_SIZE: u64 = 100 * 1024;
+
+/// The maximum size of a collation notification received by authorities, in bytes. Collation
+/// advertisements may carry more metadata than fits the default.
+pub const MAX_COLLATION_AUTHORITY_NOTIFICATION_SIZE: u64 = 1024 * 1024;
+
+/// No peer set allows notifications larger than this, in bytes.
+pub const MAX_NOTIFICATION_SIZE_CEILING: u64 = MAX_COLLATION_AUTHORITY_NOTIFICATION_SIZE;
+
+/// The default idle timeout of validation peers, which are kept warm for gossip.
+pub const DEFAULT_VALIDATION_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// The default idle timeout of collation peers, whose slots are better freed quickly.
+pub const DEFAULT_COLLATION_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The default reputation decay interval of validation peers, which are valuable to keep.
+pub const DEFAULT_VALIDATION_REPUTATION_DECAY: Duration = Duration::from_secs(60);
+
+/// The default reputation decay interval of collation peers.
+pub const DEFAULT_COLLATION_REPUTATION_DECAY: Duration = Duration::from_secs(10);
+
 /// The peer-sets and thus the protocols which are used for the network.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 pub enum PeerSet {
@@ -53,55 +89,404 @@ pub enum IsAuthority {
 	Yes,
 	/// Node is not an authority.
 	No,
+	/// Node is about to become an authority, e.g. in the next era.
+	///
+	/// Such nodes warm up their validation gossip connections like authorities, but don't accept
+	/// collations yet.
+	AboutToBeAuthority,
+}
+
+/// How messages are exchanged on a peer set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessagingStyle {
+	/// Messages are gossiped to, and relayed by, many peers.
+	Gossip,
+	/// Messages are sent directly to the peers they are meant for.
+	Directed,
+}
+
+/// The status of a protocol version of a peer set, see [`PeerSet::version_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionStatus {
+	/// The main version, which is the one we advertise.
+	Current,
+	/// An older version which is still accepted as a fallback, but about to be retired.
+	Deprecated,
+	/// A version we don't support.
+	Unknown,
+}
+
+/// Default reputation changes for the common peer behaviours on a peer set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReputationDeltas {
+	/// A well-formed and useful message.
+	pub good_message: Rep,
+	/// An invalid or unexpected message.
+	pub bad_message: Rep,
+	/// A peer not responding in time.
+	pub timeout: Rep,
+}
+
+/// How many validation gossip slots non-authorities use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GossipSlotMode {
+	/// The same slots as authorities.
+	Full,
+	/// Half of the slots of authorities, for resource constrained full nodes.
+	ReducedGossip,
+}
+
+/// The resolved configuration of a peer set, see [`PeerSet::describe_config`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerSetConfigSummary {
+	/// The configured peer set.
+	pub peer_set: PeerSet,
+	/// The role of the node.
+	pub is_authority: IsAuthority,
+	/// Number of inbound non-reserved slots.
+	pub in_peers: u32,
+	/// Number of outbound non-reserved slots.
+	pub out_peers: u32,
+	/// Whether non-reserved peers are accepted.
+	pub non_reserved_mode: sc_network::config::NonReservedPeerMode,
+	/// Maximum size of a notification, in bytes.
+	pub max_notification_size: u64,
+	/// How long a connection may stay idle before the network may drop it.
+	pub idle_timeout: Duration,
+	/// How often reputation changes of peers decay towards neutral.
+	pub reputation_decay: Duration,
+	/// The handshake to exchange when connecting, if any.
+	pub handshake: Option<Vec<u8>>,
+}
+
+/// The resolved configurations and protocol names of all peer sets, see [`peer_sets_summary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerSetsSummary {
+	/// The role of the node.
+	pub is_authority: IsAuthority,
+	/// The resolved configuration of each peer set.
+	pub configs: PerPeerSet<PeerSetConfigSummary>,
+	/// The main and fallback protocol names of each peer set.
+	pub names: PerPeerSet<ProtocolNameSet>,
+}
+
+/// Overrides of the default peer set configurations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerSetConfig {
+	/// Number of inbound collation slots of authorities.
+	pub collation_authority_in_peers: u32,
+	/// Validation gossip slots of non-authorities. Authorities always use full slots.
+	pub gossip_slot_mode: GossipSlotMode,
+	/// Which peer sets to register at all, e.g. relay chain only validators may disable
+	/// collation.
+	pub enabled: PerPeerSet<bool>,
+	/// Per peer set hint of how long connections may stay idle before being dropped.
+	pub idle_timeouts: PerPeerSet<Duration>,
+	/// Per peer set hint of how often reputation changes decay, for the peer management. Longer
+	/// intervals decay more slowly.
+	pub reputation_decay: PerPeerSet<Duration>,
+	/// Per peer set handshake subsystems advertise when connecting, e.g. a versioned role bitmap.
+	pub handshakes: PerPeerSet<Option<Vec<u8>>>,
+}
+
+impl Default for PeerSetConfig {
+	fn default() -> Self {
+		Self {
+			collation_authority_in_peers: DEFAULT_COLLATION_AUTHORITY_IN_PEERS,
+			gossip_slot_mode: GossipSlotMode::Full,
+			enabled: PerPeerSet::new_with(|_| true),
+			idle_timeouts: PerPeerSet::new_with(|peer_set| match peer_set {
+				PeerSet::Validation => DEFAULT_VALIDATION_IDLE_TIMEOUT,
+				PeerSet::Collation => DEFAULT_COLLATION_IDLE_TIMEOUT,
+			}),
+			reputation_decay: PerPeerSet::new_with(|peer_set| match peer_set {
+				PeerSet::Validation => DEFAULT_VALIDATION_REPUTATION_DECAY,
+				PeerSet::Collation => DEFAULT_COLLATION_REPUTATION_DECAY,
+			}),
+			handshakes: PerPeerSet::default(),
+		}
+	}
+}
+
+impl PeerSetConfig {
+	/// Check that the configuration is sensible for a node of the given role.
+	pub fn validate(&self, is_authority: IsAuthority) -> Result<(), InvalidPeerSetConfig> {
+		if is_authority == IsAuthority::Yes &&
+			self.enabled[PeerSet::Collation] &&
+			self.collation_authority_in_peers == 0
+		{
+			return Err(InvalidPeerSetConfig::NoCollationAuthorityInPeers)
+		}
+		Ok(())
+	}
+}
+
+/// Errors of [`validate_set_config`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SetConfigError {
+	/// The notifications protocol isn't a name of any peer set.
+	#[error("Unknown notifications protocol: `{0}`")]
+	UnknownProtocol(Cow<'static, str>),
+	/// Collation connections are only ever initiated by collators.
+	#[error("Collation peer set has {0} outbound slots")]
+	CollationOutPeers(u32),
+	/// Non-reserved peers are denied, but there are non-reserved slots.
+	///
+	/// Accepting non-reserved peers without any slots is fine, e.g. for a peer set a node of the
+	/// given role doesn't take part in.
+	#[error("Non-reserved mode {mode:?} doesn't match {in_peers} inbound and {out_peers} outbound slots")]
+	NonReservedModeMismatch {
+		/// The configured non-reserved mode.
+		mode: sc_network::config::NonReservedPeerMode,
+		/// The configured inbound slots.
+		in_peers: u32,
+		/// The configured outbound slots.
+		out_peers: u32,
+	},
+	/// The maximum notification size exceeds [`MAX_NOTIFICATION_SIZE_CEILING`].
+	#[error("Maximum notification size {0} exceeds the ceiling")]
+	NotificationSizeTooLarge(u64),
+}
+
+/// Errors of [`PeerSetConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidPeerSetConfig {
+	/// Authorities would not accept any collations.
+	#[error("Authorities need at least one inbound collation slot")]
+	NoCollationAuthorityInPeers,
 }
 
 impl PeerSet {
-	/// Get `sc_network` peer set configurations for each peerset on the default version.
+	/// The number of peer sets.
+	pub const COUNT: usize = 2;
+
+	/// All peer sets, in the order of `PeerSet::iter()`, e.g. for use in `const` contexts.
+	pub const ALL: [PeerSet; PeerSet::COUNT] = [PeerSet::Validation, PeerSet::Collation];
+
+	/// Get the index of this peer set, in `0..PeerSet::COUNT` and in the order of
+	/// `PeerSet::iter()`.
+	pub const fn index(self) -> usize {
+		match self {
+			PeerSet::Validation => 0,
+			PeerSet::Collation => 1,
+		}
+	}
+
+	/// Get the peer set at the given index, see [`PeerSet::index`].
+	pub const fn from_index(index: usize) -> Option<PeerSet> {
+		match index {
+			0 => Some(PeerSet::Validation),
+			1 => Some(PeerSet::Collation),
+			_ => None,
+		}
+	}
+
+	/// Get `sc_network` peer set configurations for each peerset, registered under its main
+	/// protocol name in `names` with the other accepted names as fallbacks.
 	///
 	/// Those should be used in the network configuration to register the protocols with the
 	/// network service.
-	pub fn get_info(self, is_authority: IsAuthority) -> NonDefaultSetConfig {
-		let version = self.get_default_version();
-		let protocol = self
-			.into_protocol_name(version)
-			.expect("default version always has protocol name; qed");
-		let max_notification_size = 100 * 1024;
+	pub fn get_info(
+		self,
+		is_authority: IsAuthority,
+		names: &PeerSetProtocolNames,
+	) -> NonDefaultSetConfig {
+		self.get_info_with_config(is_authority, &PeerSetConfig::default(), names)
+	}
+
+	/// Get `sc_network` peer set configurations for each peerset, registered under its main
+	/// protocol name in `names` and with the given overrides applied.
+	///
+	/// The overrides are expected to have been checked with [`PeerSetConfig::validate`].
+	pub fn get_info_with_config(
+		self,
+		is_authority: IsAuthority,
+		config: &PeerSetConfig,
+		names: &PeerSetProtocolNames,
+	) -> NonDefaultSetConfig {
+		NonDefaultSetConfig {
+			notifications_protocol: names.get_main_name(self),
+			fallback_names: names.fallback_names(self),
+			max_notification_size: self.get_max_notification_size(is_authority),
+			set_config: self.set_config_with_config(is_authority, config),
+		}
+	}
+
+	/// Get the `sc_network` slot configuration of this peer set for a node of the given role.
+	pub fn default_set_config(self, is_authority: IsAuthority) -> SetConfig {
+		self.set_config_with_config(is_authority, &PeerSetConfig::default())
+	}
 
+	/// Get the `sc_network` slot configuration of this peer set for a node of the given role,
+	/// with the given overrides applied.
+	pub fn set_config_with_config(
+		self,
+		is_authority: IsAuthority,
+		config: &PeerSetConfig,
+	) -> SetConfig {
 		match self {
-			PeerSet::Validation => NonDefaultSetConfig {
-				notifications_protocol: protocol,
-				fallback_names: Vec::new(),
-				max_notification_size,
-				set_config: sc_network::config::SetConfig {
-					// we allow full nodes to connect to validators for gossip
-					// to ensure any `MIN_GOSSIP_PEERS` always include reserved peers
-					// we limit the amount of non-reserved slots to be less
-					// than `MIN_GOSSIP_PEERS` in total
-					in_peers: super::MIN_GOSSIP_PEERS as u32 / 2 - 1,
-					out_peers: super::MIN_GOSSIP_PEERS as u32 / 2 - 1,
+			PeerSet::Validation => {
+				let (in_peers, out_peers) = match (is_authority, config.gossip_slot_mode) {
+					(IsAuthority::No, GossipSlotMode::ReducedGossip) => {
+						let (in_peers, out_peers) = validation_slots();
+						(in_peers / 2, out_peers / 2)
+					},
+					_ => validation_slots(),
+				};
+				SetConfig {
+					in_peers,
+					out_peers,
 					reserved_nodes: Vec::new(),
 					non_reserved_mode: sc_network::config::NonReservedPeerMode::Accept,
-				},
+				}
 			},
-			PeerSet::Collation => NonDefaultSetConfig {
-				notifications_protocol: protocol,
-				fallback_names: Vec::new(),
-				max_notification_size,
-				set_config: SetConfig {
-					// Non-authority nodes don't need to accept incoming connections on this peer set:
-					in_peers: if is_authority == IsAuthority::Yes { 100 } else { 0 },
-					out_peers: 0,
-					reserved_nodes: Vec::new(),
-					non_reserved_mode: if is_authority == IsAuthority::Yes {
-						sc_network::config::NonReservedPeerMode::Accept
-					} else {
-						sc_network::config::NonReservedPeerMode::Deny
-					},
+			PeerSet::Collation => SetConfig {
+				// Non-authority nodes don't need to accept incoming connections on this peer set,
+				// neither do nodes that are not elected yet:
+				in_peers: if is_authority == IsAuthority::Yes {
+					config.collation_authority_in_peers
+				} else {
+					0
+				},
+				out_peers: 0,
+				reserved_nodes: Vec::new(),
+				non_reserved_mode: if is_authority == IsAuthority::Yes {
+					sc_network::config::NonReservedPeerMode::Accept
+				} else {
+					sc_network::config::NonReservedPeerMode::Deny
 				},
 			},
 		}
 	}
 
+	/// Get a summary of the configuration [`PeerSet::get_info`] resolves for a node of the
+	/// given role.
+	pub fn describe_config(self, is_authority: IsAuthority) -> PeerSetConfigSummary {
+		self.describe_config_with_config(is_authority, &Default::default())
+	}
+
+	/// Get a summary of the configuration [`PeerSet::get_info_with_config`] resolves for a node
+	/// of the given role.
+	pub fn describe_config_with_config(
+		self,
+		is_authority: IsAuthority,
+		config: &PeerSetConfig,
+	) -> PeerSetConfigSummary {
+		self.summarize(is_authority, config)
+	}
+
+	fn summarize(self, is_authority: IsAuthority, config: &PeerSetConfig) -> PeerSetConfigSummary {
+		let set_config = self.set_config_with_config(is_authority, config);
+		PeerSetConfigSummary {
+			peer_set: self,
+			is_authority,
+			in_peers: set_config.in_peers,
+			out_peers: set_config.out_peers,
+			non_reserved_mode: set_config.non_reserved_mode,
+			max_notification_size: self.get_max_notification_size(is_authority),
+			idle_timeout: config.idle_timeouts[self],
+			reputation_decay: config.reputation_decay[self],
+			handshake: config.handshakes[self].clone(),
+		}
+	}
+
+	/// Get the maximum size of a notification on this peer set for a node of the given role, in
+	/// bytes.
+	pub const fn get_max_notification_size(self, is_authority: IsAuthority) -> u64 {
+		match (self, is_authority) {
+			(PeerSet::Collation, IsAuthority::Yes) => MAX_COLLATION_AUTHORITY_NOTIFICATION_SIZE,
+			_ => DEFAULT_MAX_NOTIFICATION_SIZE,
+		}
+	}
+
+	/// Whether a notification of `len` bytes exceeds the maximum notification size of this peer
+	/// set for a node of the given role, and must not be handed to subsystems.
+	pub fn is_notification_oversized(self, is_authority: IsAuthority, len: usize) -> bool {
+		len as u64 > self.get_max_notification_size(is_authority)
+	}
+
+	/// Get an upper bound of the memory used by notification buffers of this peer set, in bytes.
+	///
+	/// This assumes a single buffered notification of maximum size per non-reserved slot, as
+	/// configured by [`PeerSet::get_info`] with the given `names`.
+	pub fn notification_memory_upper_bound(
+		self,
+		is_authority: IsAuthority,
+		names: &PeerSetProtocolNames,
+	) -> u64 {
+		let info = self.get_info(is_authority, names);
+		let slots = info.set_config.in_peers as u64 + info.set_config.out_peers as u64;
+		info.max_notification_size.saturating_mul(slots)
+	}
+
+	/// Whether a node of the given role takes part in this peer set at all, i.e. has any
+	/// non-reserved slots on it.
+	///
+	/// Reserved peers, e.g. collators connecting to validators, are not taken into account.
+	pub fn is_authority_relevant(self, is_authority: IsAuthority) -> bool {
+		let set_config = self.default_set_config(is_authority);
+		set_config.in_peers > 0 || set_config.out_peers > 0
+	}
+
+	/// Whether this peer set only functions on authorities, i.e. non-authorities don't take part
+	/// in it at all, see [`PeerSet::is_authority_relevant`].
+	pub fn requires_authority(self) -> bool {
+		!self.is_authority_relevant(IsAuthority::No) && self.is_authority_relevant(IsAuthority::Yes)
+	}
+
+	/// Iterate over the peer sets authorities take part in, see [`PeerSet::is_authority_relevant`].
+	pub fn authority_peer_sets() -> impl Iterator<Item = PeerSet> {
+		PeerSet::iter().filter(|peer_set| peer_set.is_authority_relevant(IsAuthority::Yes))
+	}
+
+	/// Get the default reputation changes of this peer set.
+	///
+	/// Collation peers are few and individually valuable, so their changes are of a larger
+	/// magnitude than those of validation gossip peers.
+	pub const fn reputation_deltas(self) -> ReputationDeltas {
+		match self {
+			PeerSet::Validation => ReputationDeltas {
+				good_message: Rep::BenefitMinor("Valid gossip message"),
+				bad_message: Rep::CostMajor("Invalid gossip message"),
+				timeout: Rep::CostMinor("Gossip peer timed out"),
+			},
+			PeerSet::Collation => ReputationDeltas {
+				good_message: Rep::BenefitMajor("Valid collation message"),
+				bad_message: Rep::CostMajorRepeated("Invalid collation message"),
+				timeout: Rep::CostMinorRepeated("Collation peer timed out"),
+			},
+		}
+	}
+
+	/// Get how messages are exchanged on this peer set, e.g. to decide whether gossip specific
+	/// peer scoring applies.
+	pub const fn messaging_style(self) -> MessagingStyle {
+		match self {
+			PeerSet::Validation => MessagingStyle::Gossip,
+			PeerSet::Collation => MessagingStyle::Directed,
+		}
+	}
+
+	/// Get identifiers of the subsystems consuming the messages of this peer set, following the
+	/// variants of the peer set's wire protocol message, e.g. [`v1::ValidationProtocol`].
+	///
+	/// [`v1::ValidationProtocol`]: crate::v1::ValidationProtocol
+	pub const fn relevant_subsystems(self) -> &'static [&'static str] {
+		match self {
+			PeerSet::Validation =>
+				&["bitfield-distribution", "statement-distribution", "approval-distribution"],
+			PeerSet::Collation => &["collator-protocol"],
+		}
+	}
+
+	/// Get the short name of this peer set, used in its protocol names and labels.
+	pub const fn short_name(self) -> &'static str {
+		match self {
+			PeerSet::Validation => "validation",
+			PeerSet::Collation => "collation",
+		}
+	}
+
 	/// Get the default protocol version for this peer set.
 	pub const fn get_default_version(self) -> ProtocolVersion {
 		match self {
@@ -110,6 +495,77 @@ impl PeerSet {
 		}
 	}
 
+	/// Get the oldest protocol version of this peer set registered in `names`.
+	pub fn min_supported_version(self, names: &PeerSetProtocolNames) -> ProtocolVersion {
+		names
+			.supported_versions(self)
+			.first()
+			.copied()
+			.unwrap_or(self.get_default_version())
+	}
+
+	/// Get the newest protocol version of this peer set registered in `names`, e.g. to negotiate
+	/// the highest version supported by both sides.
+	pub fn max_supported_version(self, names: &PeerSetProtocolNames) -> ProtocolVersion {
+		names
+			.supported_versions(self)
+			.last()
+			.copied()
+			.unwrap_or(self.get_default_version())
+	}
+
+	/// Whether a node speaking the `local` version of this peer set's protocol can talk to a peer
+	/// speaking the `remote` version.
+	///
+	/// Versions are not backwards compatible, so they have to match exactly.
+	pub const fn is_version_compatible(
+		self,
+		local: ProtocolVersion,
+		remote: ProtocolVersion,
+	) -> bool {
+		local == remote
+	}
+
+	/// Get the status of the given protocol version of this peer set, as registered in `names`.
+	///
+	/// Versions newer than the main version, and disabled fallbacks, are not accepted from peers
+	/// and thus [`VersionStatus::Unknown`].
+	pub fn version_status(
+		self,
+		version: ProtocolVersion,
+		names: &PeerSetProtocolNames,
+	) -> VersionStatus {
+		let main_version = names.get_main_version(self);
+		if version == main_version {
+			VersionStatus::Current
+		} else if version < main_version &&
+			names.names.contains_key(&(self, version)) &&
+			!names.disabled_fallbacks.contains(&(self, version))
+		{
+			VersionStatus::Deprecated
+		} else {
+			VersionStatus::Unknown
+		}
+	}
+
+	/// Get the tag of the Polkadot release which introduced the given protocol version of this
+	/// peer set, if known, e.g. for migration tooling.
+	pub const fn version_introduced_in(self, version: ProtocolVersion) -> Option<&'static str> {
+		match (self, version) {
+			(PeerSet::Validation, 1) => Some("v0.9.0"),
+			(PeerSet::Collation, 1) => Some("v0.9.0"),
+			_ => None,
+		}
+	}
+
+	/// Get the protocol versions this peer set has without any [`PeerSetProtocolNames`]
+	/// configuration, i.e. from the first version up to its default version.
+	///
+	/// See [`PeerSetProtocolNames::supported_versions`] for the versions actually registered.
+	pub fn default_version_range(self) -> RangeInclusive<ProtocolVersion> {
+		1..=self.get_default_version()
+	}
+
 	/// Get the default protocol name as a static str.
 	pub const fn get_default_protocol_name(self) -> &'static str {
 		match self {
@@ -128,6 +584,40 @@ impl PeerSet {
 		}
 	}
 
+	/// Get the protocol label for metrics for the given protocol version, if any.
+	pub const fn get_protocol_label(self, version: ProtocolVersion) -> Option<&'static str> {
+		// Unfortunately, labels must be static strings, so we must manually cover them
+		// for all protocol versions here. They must read `<short_name>/<version>`.
+		match (self, version) {
+			(PeerSet::Validation, 1) => Some("validation/1"),
+			(PeerSet::Collation, 1) => Some("collation/1"),
+			_ => None,
+		}
+	}
+
+	/// Get the `sc_network` protocol id of this peer set, derived from its main name.
+	pub fn protocol_id(self, names: &PeerSetProtocolNames) -> ProtocolId {
+		ProtocolId::from(names.get_main_name(self).as_ref())
+	}
+
+	/// Get the on the wire names of the request/response protocols accompanying this peer set.
+	///
+	/// Names are built by [`ReqProtocolNames`] from the genesis hash & fork id of the given
+	/// `names`, a custom prefix of `names` is not applied to them.
+	pub fn companion_req_resp_names(self, names: &PeerSetProtocolNames) -> Vec<Cow<'static, str>> {
+		let protocols: &[Protocol] = match self {
+			PeerSet::Validation => &[Protocol::ChunkFetchingV1, Protocol::AvailableDataFetchingV1],
+			PeerSet::Collation => &[Protocol::PoVFetchingV1],
+		};
+		let req_protocol_names =
+			ReqProtocolNames::new(names.genesis_hash, names.fork_id.as_deref());
+
+		protocols
+			.iter()
+			.map(|protocol| req_protocol_names.get_name(*protocol))
+			.collect()
+	}
+
 	/// Get the protocol name associated with each peer set as understood by Substrate.
 	pub fn into_default_protocol_name(self) -> Cow<'static, str> {
 		self.get_default_protocol_name().into()
@@ -151,13 +641,99 @@ impl PeerSet {
 	}
 }
 
+// Every peer set must have a protocol label at its default version, see
+// `PeerSet::get_protocol_label`.
+const _: () = {
+	let mut index = 0;
+	while index < PeerSet::COUNT {
+		match PeerSet::from_index(index) {
+			Some(peer_set) => assert!(
+				peer_set.get_protocol_label(peer_set.get_default_version()).is_some(),
+				"a peer set is missing a protocol label for its default version",
+			),
+			None => panic!("`PeerSet::from_index` doesn't cover `PeerSet::COUNT`"),
+		}
+		index += 1;
+	}
+};
+
+/// Peer sets are ordered by their registration priority with the network service.
+///
+/// Validation is registered before collation, so that gossip slots are reserved first.
+impl Ord for PeerSet {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		let priority = |peer_set: &PeerSet| match peer_set {
+			PeerSet::Validation => 0u8,
+			PeerSet::Collation => 1,
+		};
+		priority(self).cmp(&priority(other))
+	}
+}
+
+impl PartialOrd for PeerSet {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/// A byte doesn't encode any peer set, see [`PeerSet::try_from`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown peer set byte: {0}")]
+pub struct UnknownPeerSet(pub u8);
+
+/// Compact one byte encoding of a peer set, e.g. to tag internal messages.
+///
+/// These values are part of the encoding and must not change, unlike [`PeerSet::index`].
+impl From<PeerSet> for u8 {
+	fn from(peer_set: PeerSet) -> u8 {
+		match peer_set {
+			PeerSet::Validation => 0,
+			PeerSet::Collation => 1,
+		}
+	}
+}
+
+impl TryFrom<u8> for PeerSet {
+	type Error = UnknownPeerSet;
+
+	fn try_from(byte: u8) -> Result<Self, Self::Error> {
+		match byte {
+			0 => Ok(PeerSet::Validation),
+			1 => Ok(PeerSet::Collation),
+			_ => Err(UnknownPeerSet(byte)),
+		}
+	}
+}
+
 /// A small and nifty collection that allows to store data pertaining to each peer set.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct PerPeerSet<T> {
 	validation: T,
 	collation: T,
 }
 
+impl<T> PerPeerSet<T> {
+	/// Initialize the value of each peer set with `f`, called in `PeerSet::iter()` order.
+	pub fn new_with(mut f: impl FnMut(PeerSet) -> T) -> Self {
+		Self { validation: f(PeerSet::Validation), collation: f(PeerSet::Collation) }
+	}
+
+	/// Get the value of `peer_set`.
+	pub fn get(&self, peer_set: PeerSet) -> &T {
+		&self[peer_set]
+	}
+
+	/// Get the value of `peer_set` mutably.
+	pub fn get_mut(&mut self, peer_set: PeerSet) -> &mut T {
+		&mut self[peer_set]
+	}
+
+	/// Get the value of the peer set with the given index, see [`PeerSet::index`], if any.
+	pub fn checked_index(&self, index: usize) -> Option<&T> {
+		PeerSet::from_index(index).map(|peer_set| self.get(peer_set))
+	}
+}
+
 impl<T> Index<PeerSet> for PerPeerSet<T> {
 	type Output = T;
 	fn index(&self, index: PeerSet) -> &T {
@@ -177,10 +753,2957 @@ impl<T> IndexMut<PeerSet> for PerPeerSet<T> {
 	}
 }
 
-/// Get `NonDefaultSetConfig`s for all available peer sets, at their default versions.
+/// Like [`PerPeerSet`], but stored in a `Vec` indexed by [`PeerSet::index`], so it doesn't need
+/// to be touched when adding a peer set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerPeerSetVec<T>(Vec<T>);
+
+impl<T> PerPeerSetVec<T> {
+	/// Initialize the value of each peer set with `f`, called in `PeerSet::iter()` order.
+	pub fn from_fn(f: impl FnMut(PeerSet) -> T) -> Self {
+		Self(PeerSet::iter().map(f).collect())
+	}
+}
+
+impl<T: Default> Default for PerPeerSetVec<T> {
+	fn default() -> Self {
+		Self::from_fn(|_| T::default())
+	}
+}
+
+impl<T> Index<PeerSet> for PerPeerSetVec<T> {
+	type Output = T;
+	fn index(&self, index: PeerSet) -> &T {
+		&self.0[index.index()]
+	}
+}
+
+impl<T> IndexMut<PeerSet> for PerPeerSetVec<T> {
+	fn index_mut(&mut self, index: PeerSet) -> &mut T {
+		&mut self.0[index.index()]
+	}
+}
+
+/// Get the `(in_peers, out_peers)` non-reserved slots of the validation peer set.
+///
+/// We allow full nodes to connect to validators for gossip. To ensure any `MIN_GOSSIP_PEERS`
+/// always include reserved peers, we limit the amount of non-reserved slots to be less than
+/// `MIN_GOSSIP_PEERS` in total. Slot counts are zero at minimum.
+pub fn validation_slots() -> (u32, u32) {
+	validation_slots_for(super::MIN_GOSSIP_PEERS)
+}
+
+/// Get the number of non-reserved outgoing gossip connections a node of the given role opens.
+///
+/// Only the validation peer set is gossip based, collation connections are point to point.
+pub fn gossip_out_capacity(is_authority: IsAuthority) -> u32 {
+	PeerSet::Validation.default_set_config(is_authority).out_peers
+}
+
+fn validation_slots_for(min_gossip_peers: usize) -> (u32, u32) {
+	let slots = (min_gossip_peers as u32 / 2).saturating_sub(1);
+	(slots, slots)
+}
+
+/// Get `NonDefaultSetConfig`s for all available peer sets, registered under their names in
+/// `names`.
 ///
 /// Should be used during network configuration (added to [`NetworkConfiguration::extra_sets`])
 /// or shortly after startup to register the protocols with the network service.
-pub fn peer_sets_info(is_authority: IsAuthority) -> Vec<sc_network::config::NonDefaultSetConfig> {
-	PeerSet::iter().map(|s| s.get_info(is_authority)).collect()
+///
+/// The configurations are returned in registration order, see the [`Ord`] implementation of
+/// [`PeerSet`].
+pub fn peer_sets_info(
+	is_authority: IsAuthority,
+	names: &PeerSetProtocolNames,
+) -> Vec<sc_network::config::NonDefaultSetConfig> {
+	let config = PeerSetConfig::default();
+	let peer_sets = peer_sets_to_register(is_authority, &config);
+	log_resolved_configs(is_authority, &config, &peer_sets);
+	peer_sets.into_iter().map(|s| s.get_info(is_authority, names)).collect()
+}
+
+/// Log the configurations of the given peer sets once they are resolved for registration.
+fn log_resolved_configs(is_authority: IsAuthority, config: &PeerSetConfig, peer_sets: &[PeerSet]) {
+	gum::debug!(
+		target: LOG_TARGET,
+		?is_authority,
+		summaries = ?peer_sets
+			.iter()
+			.map(|peer_set| peer_set.summarize(is_authority, config))
+			.collect::<Vec<_>>(),
+		"Resolved peer set configurations",
+	);
+}
+
+/// Dump the configurations of all peer sets as JSON, e.g. for diagnostics.
+///
+/// Each peer set is described by its names, slots and maximum notification size, in
+/// registration order.
+#[cfg(feature = "serde")]
+pub fn peer_sets_info_json(
+	is_authority: IsAuthority,
+	names: &PeerSetProtocolNames,
+) -> serde_json::Value {
+	peer_sets_by_priority()
+		.into_iter()
+		.map(|peer_set| {
+			let info = peer_set.get_info(is_authority, names);
+			serde_json::json!({
+				"peer_set": peer_set.short_name(),
+				"name": info.notifications_protocol,
+				"fallback_names": info.fallback_names,
+				"in_peers": info.set_config.in_peers,
+				"out_peers": info.set_config.out_peers,
+				"max_notification_size": info.max_notification_size,
+			})
+		})
+		.collect()
+}
+
+/// Summarize the configurations and protocol names of all peer sets for a node of the given
+/// role, e.g. for a debug endpoint.
+pub fn peer_sets_summary(
+	is_authority: IsAuthority,
+	names: &PeerSetProtocolNames,
+) -> PeerSetsSummary {
+	PeerSetsSummary {
+		is_authority,
+		configs: PerPeerSet::new_with(|peer_set| peer_set.describe_config(is_authority)),
+		names: PerPeerSet::new_with(|peer_set| names.protocol_name_set(peer_set)),
+	}
+}
+
+/// Register all peer sets with the network `config`, see [`peer_sets_info`].
+///
+/// Returns the number of peer sets added to [`NetworkConfiguration::extra_sets`].
+pub fn add_peer_sets(
+	config: &mut NetworkConfiguration,
+	is_authority: IsAuthority,
+	names: &PeerSetProtocolNames,
+) -> usize {
+	let peer_sets = peer_sets_info(is_authority, names);
+	let count = peer_sets.len();
+	config.extra_sets.extend(peer_sets);
+	count
+}
+
+/// Assert that each peer set is registered exactly once among `configs`, e.g. in tests spinning
+/// up a node.
+///
+/// A config registers the peer set its `notifications_protocol` resolves to with `names`, which
+/// covers both main and legacy names.
+///
+/// Panics if a peer set is missing or registered more than once.
+pub fn assert_all_peer_sets_registered(
+	configs: &[NonDefaultSetConfig],
+	names: &PeerSetProtocolNames,
+) {
+	let mut registered = PerPeerSet::<usize>::default();
+	for config in configs {
+		if let Some((peer_set, _)) = names.try_get_protocol(&config.notifications_protocol) {
+			registered[peer_set] += 1;
+		}
+	}
+	for peer_set in PeerSet::iter() {
+		assert_eq!(
+			registered[peer_set],
+			1,
+			"{:?} (`{}`) must be registered exactly once",
+			peer_set,
+			names.get_main_name(peer_set),
+		);
+	}
+}
+
+/// Check a peer set configuration against the invariants of its peer set, before handing it to
+/// the network.
+///
+/// The peer set is resolved from the notifications protocol with `names`, which covers both main
+/// and legacy names.
+pub fn validate_set_config(
+	cfg: &NonDefaultSetConfig,
+	names: &PeerSetProtocolNames,
+) -> Result<(), SetConfigError> {
+	use sc_network::config::NonReservedPeerMode;
+
+	let peer_set = names
+		.try_get_protocol(&cfg.notifications_protocol)
+		.map(|(peer_set, _)| peer_set)
+		.ok_or_else(|| SetConfigError::UnknownProtocol(cfg.notifications_protocol.clone()))?;
+	let SetConfig { in_peers, out_peers, non_reserved_mode: mode, .. } = cfg.set_config;
+
+	if peer_set == PeerSet::Collation && out_peers > 0 {
+		return Err(SetConfigError::CollationOutPeers(out_peers))
+	}
+	let has_slots = in_peers > 0 || out_peers > 0;
+	if has_slots && mode == NonReservedPeerMode::Deny {
+		return Err(SetConfigError::NonReservedModeMismatch { mode, in_peers, out_peers })
+	}
+	if cfg.max_notification_size > MAX_NOTIFICATION_SIZE_CEILING {
+		return Err(SetConfigError::NotificationSizeTooLarge(cfg.max_notification_size))
+	}
+	Ok(())
+}
+
+/// Whether two peer set configurations are equivalent, e.g. to compare against golden
+/// configurations in tests.
+///
+/// Compares names, maximum notification sizes and slot configurations.
+pub fn set_configs_equivalent(a: &NonDefaultSetConfig, b: &NonDefaultSetConfig) -> bool {
+	a.notifications_protocol == b.notifications_protocol &&
+		a.fallback_names == b.fallback_names &&
+		a.max_notification_size == b.max_notification_size &&
+		a.set_config.in_peers == b.set_config.in_peers &&
+		a.set_config.out_peers == b.set_config.out_peers &&
+		a.set_config.reserved_nodes == b.set_config.reserved_nodes &&
+		a.set_config.non_reserved_mode == b.set_config.non_reserved_mode
+}
+
+/// Get `NonDefaultSetConfig`s for all enabled peer sets, registered under their names in `names`
+/// and with the given overrides applied.
+pub fn peer_sets_info_with_config(
+	is_authority: IsAuthority,
+	config: &PeerSetConfig,
+	names: &PeerSetProtocolNames,
+) -> Result<Vec<NonDefaultSetConfig>, InvalidPeerSetConfig> {
+	config.validate(is_authority)?;
+	let peer_sets = peer_sets_to_register(is_authority, config);
+	log_resolved_configs(is_authority, config, &peer_sets);
+	Ok(peer_sets
+		.into_iter()
+		.map(|s| s.get_info_with_config(is_authority, config, names))
+		.collect())
+}
+
+/// Get the peer sets a node of the given role registers with the network given the `config`, in
+/// order of their priority for that role.
+///
+/// Every role registers every peer set enabled in `config`. Non-authorities register collation
+/// in [`NonReservedPeerMode::Deny`](sc_network::config::NonReservedPeerMode::Deny) mode, to
+/// still recognize the protocol.
+pub fn peer_sets_to_register(is_authority: IsAuthority, config: &PeerSetConfig) -> Vec<PeerSet> {
+	peer_sets_ordered_by_priority(is_authority)
+		.into_iter()
+		.filter(|peer_set| config.enabled[*peer_set])
+		.collect()
+}
+
+/// Get the peer sets a node of the given role registers, with their maximum notification size,
+/// sorted ascending by that size.
+///
+/// Peer sets of the same size keep their registration order.
+pub fn peer_sets_by_notification_size(is_authority: IsAuthority) -> Vec<(PeerSet, u64)> {
+	let mut peer_sets: Vec<_> = peer_sets_to_register(is_authority, &PeerSetConfig::default())
+		.into_iter()
+		.map(|peer_set| (peer_set, peer_set.get_max_notification_size(is_authority)))
+		.collect();
+	peer_sets.sort_by_key(|(_, size)| *size);
+	peer_sets
+}
+
+/// Get all peer sets ordered by their priority for a node of the given role, e.g. to decide which
+/// ones get slots first.
+///
+/// Peer sets the role takes part in come first, then gossip based ones, and ties are broken by
+/// the registration order of [`PeerSet`]'s `Ord`.
+pub fn peer_sets_ordered_by_priority(is_authority: IsAuthority) -> Vec<PeerSet> {
+	let mut peer_sets = peer_sets_by_priority();
+	peer_sets.sort_by_key(|peer_set| {
+		(
+			!peer_set.is_authority_relevant(is_authority),
+			peer_set.messaging_style() != MessagingStyle::Gossip,
+			*peer_set,
+		)
+	});
+	peer_sets
+}
+
+/// All peer sets, in registration order.
+fn peer_sets_by_priority() -> Vec<PeerSet> {
+	let mut peer_sets: Vec<_> = PeerSet::iter().collect();
+	peer_sets.sort();
+	peer_sets
+}
+
+/// Get the peer sets worth enabling before the chain is synced.
+///
+/// Validation gossip is about the most recent relay chain blocks and thus pointless before the
+/// node is synced, whereas collation connections are established on request.
+pub fn peer_sets_pre_sync() -> Vec<PeerSet> {
+	PeerSet::iter()
+		.filter(|peer_set| match peer_set {
+			PeerSet::Validation => false,
+			PeerSet::Collation => true,
+		})
+		.collect()
+}
+
+/// Get the `(in_peers, out_peers)` slots of all peer sets.
+pub fn slot_summary(is_authority: IsAuthority) -> PerPeerSet<(u32, u32)> {
+	let mut summary = PerPeerSet::default();
+	for peer_set in PeerSet::iter() {
+		let set_config = peer_set.default_set_config(is_authority);
+		summary[peer_set] = (set_config.in_peers, set_config.out_peers);
+	}
+	summary
+}
+
+/// Get all the protocol labels [`PeerSet::get_protocol_label`] can return, for all peer sets
+/// and supported protocol versions.
+///
+/// Can be used to register metrics label sets upfront.
+pub fn all_protocol_labels() -> Vec<&'static str> {
+	PeerSet::iter()
+		.flat_map(|peer_set| {
+			peer_set
+				.default_version_range()
+				.filter_map(move |version| peer_set.get_protocol_label(version))
+		})
+		.collect()
+}
+
+/// Get the `(peer set label, protocol label)` pair of each peer set at its default version, as
+/// used to register metrics.
+pub fn metric_label_pairs() -> Vec<(&'static str, &'static str)> {
+	PeerSet::iter()
+		.map(|peer_set| {
+			let protocol_label = peer_set
+				.get_protocol_label(peer_set.get_default_version())
+				.expect("every peer set has a protocol label at its default version; qed");
+			(peer_set.short_name(), protocol_label)
+		})
+		.collect()
+}
+
+/// Two different protocols, or protocol versions, share the same on the wire name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+	"Protocol {protocol:?} (version {version}) has the same on-the-wire name as protocol {existing_protocol:?} (version {existing_version}): `{name}`."
+)]
+pub struct ProtocolNameCollision {
+	/// The contested on the wire name.
+	pub name: Cow<'static, str>,
+	/// The protocol which was about to be registered.
+	pub protocol: PeerSet,
+	/// The version which was about to be registered.
+	pub version: ProtocolVersion,
+	/// The protocol already registered under `name`.
+	pub existing_protocol: PeerSet,
+	/// The version already registered under `name`.
+	pub existing_version: ProtocolVersion,
+}
+
+/// Why a protocol name couldn't be resolved, see
+/// [`PeerSetProtocolNames::try_get_protocol_with_reason`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LookupMiss {
+	/// The name is well-formed, but for the genesis hash of another chain.
+	#[error("Protocol name is for another chain")]
+	WrongGenesis,
+	/// The name is well-formed and for this chain, but for an unknown fork id.
+	#[error("Protocol name is for another fork")]
+	WrongFork,
+	/// The name is well-formed and for this chain and fork, but of an unsupported version.
+	#[error("Protocol {0:?} is not supported at version {1}")]
+	UnsupportedVersion(PeerSet, ProtocolVersion),
+	/// The name is not shaped like a peer set protocol name.
+	#[error("Protocol name is not a peer set protocol name")]
+	UnknownShape,
+}
+
+/// A protocol version is not supported by a [`PeerSetProtocolNames`] instance.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Protocol {protocol:?} is not supported at version {version}")]
+pub struct UnsupportedVersion {
+	/// The requested protocol.
+	pub protocol: PeerSet,
+	/// The requested, unsupported version.
+	pub version: ProtocolVersion,
+}
+
+/// A hex encoded genesis hash can't be used, see [`PeerSetProtocolNames::from_hex_genesis`].
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum HexDecodeError {
+	/// The genesis hash isn't a valid hex encoded hash.
+	#[error("Invalid hex encoded genesis hash: {0}")]
+	InvalidHex(#[from] hex::FromHexError),
+	/// The genesis hash is zero, most likely because it wasn't initialized.
+	#[error("The genesis hash is zero")]
+	ZeroGenesisHash,
+}
+
+/// Errors building [`PeerSetProtocolNames`], see [`PeerSetProtocolNamesBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProtocolNameError {
+	/// Two protocols share the same name.
+	#[error(transparent)]
+	Collision(#[from] ProtocolNameCollision),
+	/// The custom prefix doesn't start with a `/`, or ends with one.
+	#[error("Invalid protocol name prefix: `{0}`")]
+	InvalidPrefix(String),
+	/// The versions of a peer set don't include its default version.
+	#[error("Peer set {0:?} is not registered at its default version")]
+	MissingDefaultVersion(PeerSet),
+	/// The fallback of a peer set's main version was disabled.
+	#[error("Peer set {0:?} can't disable its main version {1}")]
+	DisabledMainVersion(PeerSet, ProtocolVersion),
+	/// The versions of a peer set don't include its main version.
+	#[error("Peer set {0:?} is not registered at its main version {1}")]
+	MissingMainVersion(PeerSet, ProtocolVersion),
+	/// A custom short name is empty or contains a `/`.
+	#[error("Invalid protocol short name: `{0}`")]
+	InvalidShortName(&'static str),
+	/// The genesis hash is zero, most likely because it wasn't initialized.
+	#[error("The genesis hash is zero")]
+	ZeroGenesisHash,
+	/// A version of a peer set was registered twice, in strict mode.
+	#[error("Peer set {0:?} (version {1}) is registered twice")]
+	DuplicateVersion(PeerSet, ProtocolVersion),
+}
+
+/// Differences between the protocol names of two [`PeerSetProtocolNames`], see
+/// [`PeerSetProtocolNames::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtocolNameDiff {
+	/// Names only resolved by the other instance.
+	pub added: Vec<Cow<'static, str>>,
+	/// Names only resolved by this instance.
+	pub removed: Vec<Cow<'static, str>>,
+	/// Names resolved by both instances, but to a different peer set or version.
+	pub changed: Vec<Cow<'static, str>>,
+}
+
+/// A protocol name resolved by [`PeerSetProtocolNames::try_get_protocol_full`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedProtocol {
+	/// The peer set of the protocol.
+	pub peer_set: PeerSet,
+	/// The version of the protocol.
+	pub version: ProtocolVersion,
+	/// The fork id the name was registered under, if any.
+	pub fork_id: Option<String>,
+}
+
+/// The protocol names of a peer set, see [`PeerSetProtocolNames::protocol_name_set`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolNameSet {
+	/// The main protocol name, see [`PeerSetProtocolNames::get_main_name`].
+	pub main: Cow<'static, str>,
+	/// The fallback protocol names, see [`PeerSetProtocolNames::fallback_names`].
+	pub fallbacks: Vec<Cow<'static, str>>,
+}
+
+/// On the wire protocol name to [`PeerSet`] mapping, using genesis hash & fork id.
+///
+/// Legacy protocol names (without genesis hash) are resolved as well.
+#[derive(Debug, Clone)]
+pub struct PeerSetProtocolNames {
+	genesis_hash: Hash,
+	/// The fork id the names were built for, if any.
+	fork_id: Option<String>,
+	/// The hex encoded `/<genesis_hash>[/<fork_id>]` prefix, computed once at construction.
+	prefix: String,
+	protocols: HashMap<Cow<'static, str>, (PeerSet, ProtocolVersion)>,
+	names: HashMap<(PeerSet, ProtocolVersion), Cow<'static, str>>,
+	/// The short names used in main names, see [`PeerSet::short_name`].
+	short_names: PerPeerSet<&'static str>,
+	/// The version of the main name of each peer set, see
+	/// [`PeerSetProtocolNamesBuilder::main_version`].
+	main_versions: PerPeerSet<ProtocolVersion>,
+	/// Versions no longer accepted as fallbacks, see
+	/// [`PeerSetProtocolNamesBuilder::disable_fallback`].
+	disabled_fallbacks: HashSet<(PeerSet, ProtocolVersion)>,
+}
+
+/// Two [`PeerSetProtocolNames`] are equal if they resolve the same names for the same chain,
+/// regardless of the order the names were registered in.
+impl PartialEq for PeerSetProtocolNames {
+	fn eq(&self, other: &Self) -> bool {
+		// The fork id is part of the prefix, and `names` is derived from `protocols`.
+		self.genesis_hash == other.genesis_hash &&
+			self.prefix == other.prefix &&
+			self.protocols == other.protocols &&
+			self.main_versions == other.main_versions &&
+			self.disabled_fallbacks == other.disabled_fallbacks
+	}
+}
+
+impl Eq for PeerSetProtocolNames {}
+
+impl PeerSetProtocolNames {
+	/// Construct [`PeerSetProtocolNames`] from `genesis_hash` and `fork_id`.
+	///
+	/// Panics if the genesis hash is zero or two protocols end up with the same name, see
+	/// [`PeerSetProtocolNames::try_new`] for a fallible version.
+	pub fn new(genesis_hash: Hash, fork_id: Option<&str>) -> Self {
+		Self::try_new(genesis_hash, fork_id).unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	/// Construct [`PeerSetProtocolNames`] from `genesis_hash` and `fork_id`, failing if the
+	/// genesis hash is zero, e.g. because the chain wasn't initialized.
+	pub fn try_new(genesis_hash: Hash, fork_id: Option<&str>) -> Result<Self, ProtocolNameError> {
+		let names = Self::builder()
+			.genesis(genesis_hash)
+			.fork_id(fork_id.map(ToOwned::to_owned))
+			.build()?;
+		debug_assert!(
+			names.generated_names_are_not_legacy(),
+			"generated protocol names are prefixed by the genesis hash, legacy ones by `/polkadot`",
+		);
+		Ok(names)
+	}
+
+	/// Check that none of the generated, i.e. main, names is also a legacy name.
+	fn generated_names_are_not_legacy(&self) -> bool {
+		self.names.values().all(|name| PeerSet::try_from_protocol_name(name).is_none())
+	}
+
+	/// Construct [`PeerSetProtocolNames`] registering exactly the given `versions` per peer set,
+	/// along with the legacy names.
+	///
+	/// The versions of each peer set must include its default version, and the genesis hash must
+	/// not be zero.
+	pub fn new_with_versions(
+		genesis_hash: Hash,
+		fork_id: Option<&str>,
+		versions: &PerPeerSet<Vec<ProtocolVersion>>,
+	) -> Result<Self, ProtocolNameError> {
+		PeerSet::iter()
+			.fold(Self::builder().genesis(genesis_hash), |builder, peer_set| {
+				builder.versions(peer_set, versions[peer_set].clone())
+			})
+			.fork_id(fork_id.map(ToOwned::to_owned))
+			.build()
+	}
+
+	/// Construct [`PeerSetProtocolNames`] resolving only the main name of each peer set, without
+	/// the legacy names, e.g. for light clients.
+	///
+	/// Panics if the genesis hash is zero or two protocols end up with the same name.
+	pub fn main_only(genesis_hash: Hash, fork_id: Option<&str>) -> Self {
+		Self::builder()
+			.genesis(genesis_hash)
+			.fork_id(fork_id.map(ToOwned::to_owned))
+			.with_legacy(false)
+			.build()
+			.unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	/// Construct [`PeerSetProtocolNames`] from a hex encoded genesis hash, with or without `0x`
+	/// prefix, e.g. as found in chain specs.
+	pub fn from_hex_genesis(
+		genesis_hex: &str,
+		fork_id: Option<&str>,
+	) -> Result<Self, HexDecodeError> {
+		let genesis_hex = genesis_hex.strip_prefix("0x").unwrap_or(genesis_hex);
+		let mut genesis_hash = Hash::zero();
+		hex::decode_to_slice(genesis_hex, genesis_hash.as_bytes_mut())?;
+		Self::builder()
+			.genesis(genesis_hash)
+			.fork_id(fork_id.map(ToOwned::to_owned))
+			.build()
+			.map_err(|err| match err {
+				ProtocolNameError::ZeroGenesisHash => HexDecodeError::ZeroGenesisHash,
+				err => panic!("default protocol names never collide: {}", err),
+			})
+	}
+
+	/// Start building [`PeerSetProtocolNames`], e.g. to register multiple versions or to use a
+	/// custom prefix.
+	pub fn builder() -> PeerSetProtocolNamesBuilder {
+		PeerSetProtocolNamesBuilder::default()
+	}
+
+	/// Additionally resolve the protocol names of another `fork_id` of the same chain.
+	///
+	/// This is useful to recognize peers on both sides of a fork id change. Main names keep
+	/// referring to the original fork id, and legacy names don't depend on the fork id at all.
+	pub fn with_additional_fork(mut self, fork_id: &str) -> Result<Self, ProtocolNameCollision> {
+		let prefix = Self::name_prefix(&self.genesis_hash, Some(fork_id));
+		for protocol in PeerSet::iter() {
+			let version = self.main_versions[protocol];
+			let name = Self::name_with_prefix(&prefix, self.short_names[protocol], version);
+			Self::try_insert_protocol(&mut self.protocols, name, protocol, version)?;
+		}
+		Ok(self)
+	}
+
+	/// Re-derive all genesis prefixed names for `new_genesis`.
+	///
+	/// Fork ids, including additional ones, and legacy names are preserved. Names under a custom
+	/// prefix not derived from the genesis hash are left as they are.
+	pub fn rekey_genesis(self, new_genesis: Hash) -> Result<Self, ProtocolNameCollision> {
+		let old_prefix = Self::name_prefix(&self.genesis_hash, None);
+		let new_prefix = Self::name_prefix(&new_genesis, None);
+		let rekey = |name: &str| -> Option<String> {
+			let rest = name.strip_prefix(old_prefix.as_str())?;
+			(rest.is_empty() || rest.starts_with('/')).then(|| format!("{}{}", new_prefix, rest))
+		};
+
+		let mut protocols = HashMap::with_capacity(self.protocols.len());
+		for (name, (protocol, version)) in self.protocols {
+			let name = rekey(&name).map_or(name, Cow::Owned);
+			Self::try_insert_protocol(&mut protocols, name, protocol, version)?;
+		}
+		let names = self
+			.names
+			.into_iter()
+			.map(|(key, name)| (key, rekey(&name).map_or(name, Cow::Owned)))
+			.collect();
+		let prefix = rekey(&self.prefix).unwrap_or(self.prefix);
+
+		Ok(Self {
+			genesis_hash: new_genesis,
+			fork_id: self.fork_id,
+			prefix,
+			protocols,
+			names,
+			short_names: self.short_names,
+			main_versions: self.main_versions,
+			disabled_fallbacks: self.disabled_fallbacks,
+		})
+	}
+
+	/// Register a protocol name, unless it is already taken by another protocol or version.
+	fn try_insert_protocol(
+		protocols: &mut HashMap<Cow<'static, str>, (PeerSet, ProtocolVersion)>,
+		name: Cow<'static, str>,
+		protocol: PeerSet,
+		version: ProtocolVersion,
+	) -> Result<(), ProtocolNameCollision> {
+		match protocols.entry(name) {
+			Entry::Vacant(entry) => {
+				entry.insert((protocol, version));
+				Ok(())
+			},
+			Entry::Occupied(entry) if *entry.get() == (protocol, version) => Ok(()),
+			Entry::Occupied(entry) => Err(ProtocolNameCollision {
+				name: entry.key().clone(),
+				protocol,
+				version,
+				existing_protocol: entry.get().0,
+				existing_version: entry.get().1,
+			}),
+		}
+	}
+
+	/// Lookup the protocol using its on the wire name.
+	pub fn try_get_protocol(&self, name: &Cow<'static, str>) -> Option<(PeerSet, ProtocolVersion)> {
+		self.try_get_protocol_str(name)
+	}
+
+	/// Lookup the protocol using its on the wire name, without wrapping it into a `Cow`.
+	pub fn try_get_protocol_str(&self, name: &str) -> Option<(PeerSet, ProtocolVersion)> {
+		// Names come from the wire, don't bother hashing overly long ones.
+		if name.len() > MAX_PROTOCOL_NAME_LEN {
+			return None
+		}
+		self.protocols.get(name).copied()
+	}
+
+	/// Whether the on the wire names `a` and `b` resolve to the same peer set, regardless of
+	/// versions. False if either name is unknown.
+	pub fn same_peer_set(&self, a: &Cow<'static, str>, b: &Cow<'static, str>) -> bool {
+		match (self.try_get_protocol(a), self.try_get_protocol(b)) {
+			(Some((a, _)), Some((b, _))) => a == b,
+			_ => false,
+		}
+	}
+
+	/// Lookup the protocol using its on the wire name, explaining why it couldn't be resolved.
+	///
+	/// Meant for logging, as classifying a miss is more expensive than the lookup itself.
+	pub fn try_get_protocol_with_reason(
+		&self,
+		name: &Cow<'static, str>,
+	) -> Result<(PeerSet, ProtocolVersion), LookupMiss> {
+		if let Some(protocol) = self.try_get_protocol(name) {
+			return Ok(protocol)
+		}
+		if name.len() > MAX_PROTOCOL_NAME_LEN {
+			return Err(LookupMiss::UnknownShape)
+		}
+
+		let segments: Vec<&str> = name.split('/').collect();
+		let (genesis_hash, fork_id, short_name, version) = match segments.as_slice() {
+			["", genesis_hash, short_name, version] => (genesis_hash, None, short_name, version),
+			["", genesis_hash, fork_id, short_name, version] =>
+				(genesis_hash, Some(*fork_id), short_name, version),
+			_ => return Err(LookupMiss::UnknownShape),
+		};
+		let genesis_hash = hex::decode(genesis_hash)
+			.ok()
+			.filter(|bytes| bytes.len() == Hash::len_bytes())
+			.map(|bytes| Hash::from_slice(&bytes))
+			.ok_or(LookupMiss::UnknownShape)?;
+		let peer_set = PeerSet::iter()
+			.find(|peer_set| self.short_names[*peer_set] == *short_name)
+			.ok_or(LookupMiss::UnknownShape)?;
+		let version = version.parse().map_err(|_| LookupMiss::UnknownShape)?;
+
+		if genesis_hash != self.genesis_hash {
+			return Err(LookupMiss::WrongGenesis)
+		}
+		// Whether any name is registered under exactly this prefix.
+		let prefix = format!("{}/", Self::name_prefix(&genesis_hash, fork_id));
+		let known_prefix = self
+			.protocols
+			.keys()
+			.filter_map(|name| name.strip_prefix(prefix.as_str()))
+			.any(|rest| rest.matches('/').count() == 1);
+		if known_prefix {
+			Err(LookupMiss::UnsupportedVersion(peer_set, version))
+		} else {
+			Err(LookupMiss::WrongFork)
+		}
+	}
+
+	/// Lookup the protocol using its on the wire name, along with the fork id it was registered
+	/// under.
+	///
+	/// The fork id is `None` for legacy names and names without a fork id.
+	pub fn try_get_protocol_full(&self, name: &Cow<'static, str>) -> Option<ResolvedProtocol> {
+		let (peer_set, version) = self.try_get_protocol(name)?;
+		let fork_id = name
+			.strip_prefix(Self::name_prefix(&self.genesis_hash, None).as_str())
+			.and_then(|rest| {
+				rest.strip_suffix(&*Self::name_with_prefix("", self.short_names[peer_set], version))
+			})
+			.and_then(|fork_id| fork_id.strip_prefix('/'))
+			.map(ToOwned::to_owned);
+		Some(ResolvedProtocol { peer_set, version, fork_id })
+	}
+
+	/// Get the main protocol name. It's used by the networking for keeping track
+	/// of peersets and connections.
+	pub fn get_main_name(&self, protocol: PeerSet) -> Cow<'static, str> {
+		self.get_name(protocol, self.get_main_version(protocol))
+	}
+
+	/// Get the version of the main protocol name, the default version unless overridden with
+	/// [`PeerSetProtocolNamesBuilder::main_version`].
+	pub fn get_main_version(&self, protocol: PeerSet) -> ProtocolVersion {
+		self.main_versions[protocol]
+	}
+
+	/// Get the main and fallback protocol names of the given peer set.
+	pub fn protocol_name_set(&self, protocol: PeerSet) -> ProtocolNameSet {
+		ProtocolNameSet {
+			main: self.get_main_name(protocol),
+			fallbacks: self.fallback_names(protocol),
+		}
+	}
+
+	/// Get the main protocol name of the given peer set, followed by its fallback names, without
+	/// duplicates.
+	pub fn all_names(&self, protocol: PeerSet) -> Vec<Cow<'static, str>> {
+		let mut names = vec![self.get_main_name(protocol)];
+		for name in self.fallback_names(protocol) {
+			if !names.contains(&name) {
+				names.push(name);
+			}
+		}
+		names
+	}
+
+	/// Get the fallback protocol names of the given peer set: the names of the registered
+	/// versions older than the main version, newest first, followed by
+	/// [`PeerSetProtocolNames::get_fallback_names`].
+	pub fn fallback_names(&self, protocol: PeerSet) -> Vec<Cow<'static, str>> {
+		let main_version = self.get_main_version(protocol);
+		let mut older = self
+			.names
+			.iter()
+			.filter(|((peer_set, version), _)| *peer_set == protocol && *version < main_version)
+			.map(|((_, version), name)| (*version, name.clone()))
+			.collect::<Vec<_>>();
+		older.sort_by(|(a, _), (b, _)| b.cmp(a));
+		older
+			.into_iter()
+			.map(|(_, name)| name)
+			.chain(Self::get_fallback_names(protocol))
+			.filter(|name| self.is_accepted(name))
+			.collect()
+	}
+
+	/// Whether connections using the given protocol name are accepted, i.e. the name resolves
+	/// and its version's fallback wasn't disabled.
+	pub fn is_accepted(&self, name: &str) -> bool {
+		matches!(
+			self.try_get_protocol_str(name),
+			Some(protocol) if !self.disabled_fallbacks.contains(&protocol)
+		)
+	}
+
+	/// Get the main protocol name of the peer set consumed by the given subsystem, see
+	/// [`PeerSet::relevant_subsystems`].
+	pub fn name_for_subsystem(&self, subsystem: &str) -> Option<Cow<'static, str>> {
+		PeerSet::iter()
+			.find(|peer_set| peer_set.relevant_subsystems().contains(&subsystem))
+			.map(|peer_set| self.get_main_name(peer_set))
+	}
+
+	/// Get the protocol name for a specific version.
+	pub fn get_name(&self, protocol: PeerSet, version: ProtocolVersion) -> Cow<'static, str> {
+		self.names
+			.get(&(protocol, version))
+			.expect("Protocols & versions are specified via enums defined above, and they are all registered in `new()`; qed")
+			.clone()
+	}
+
+	/// Get the protocol name for a specific version, if that version is supported, i.e. is the
+	/// default version or was registered explicitly.
+	pub fn try_get_name(
+		&self,
+		protocol: PeerSet,
+		version: ProtocolVersion,
+	) -> Result<Cow<'static, str>, UnsupportedVersion> {
+		self.names
+			.get(&(protocol, version))
+			.cloned()
+			.ok_or(UnsupportedVersion { protocol, version })
+	}
+
+	/// Get the versions registered for `protocol`, from the oldest to the newest.
+	///
+	/// Versions in between which aren't registered are left out.
+	pub fn supported_versions(&self, protocol: PeerSet) -> Vec<ProtocolVersion> {
+		let mut versions: Vec<_> = self
+			.names
+			.keys()
+			.filter(|(peer_set, _)| *peer_set == protocol)
+			.map(|(_, version)| *version)
+			.collect();
+		versions.sort_unstable();
+		versions
+	}
+
+	/// Get the name of the highest registered version of `protocol` which is compatible with one
+	/// of the `remote_versions`, if any.
+	///
+	/// Versions whose fallback was disabled are never negotiated.
+	pub fn negotiated_name(
+		&self,
+		protocol: PeerSet,
+		remote_versions: &[ProtocolVersion],
+	) -> Option<Cow<'static, str>> {
+		self.names
+			.iter()
+			.filter(|((peer_set, version), _)| {
+				*peer_set == protocol &&
+					!self.disabled_fallbacks.contains(&(*peer_set, *version)) &&
+					remote_versions
+						.iter()
+						.any(|remote| protocol.is_version_compatible(*version, *remote))
+			})
+			.max_by_key(|((_, version), _)| *version)
+			.map(|(_, name)| name.clone())
+	}
+
+	/// Whether `name` is a legacy protocol name, i.e. without genesis hash, resolved by these
+	/// names.
+	///
+	/// Useful to keep track of peers still relying on legacy names.
+	pub fn is_legacy_name(&self, name: &Cow<'static, str>) -> bool {
+		self.protocols.contains_key(name) && PeerSet::try_from_protocol_name(name).is_some()
+	}
+
+	/// Get the fallback protocol names of the given peer set, as understood by Substrate.
+	pub fn get_fallback_names(protocol: PeerSet) -> Vec<Cow<'static, str>> {
+		std::iter::once(protocol.into_default_protocol_name()).collect()
+	}
+
+	/// Check, per peer set, whether the `offered` protocol names only contain legacy or older
+	/// names although we support the main one, as it would be the case in a downgrade attack.
+	///
+	/// Offers are classified by the version they resolve to, so the main version under an
+	/// additional fork id, see [`PeerSetProtocolNames::with_additional_fork`], is no downgrade.
+	/// Peer sets without any offered name are not considered downgraded.
+	pub fn is_forced_downgrade(&self, offered: &[String]) -> PerPeerSet<bool> {
+		let mut offers_main = PerPeerSet::<bool>::default();
+		let mut offers_other = PerPeerSet::<bool>::default();
+		for name in offered {
+			if let Some((peer_set, version)) = self.protocols.get(name.as_str()) {
+				let is_older = *version < self.main_versions[*peer_set] ||
+					name.as_str() == peer_set.get_default_protocol_name();
+				if is_older {
+					offers_other[*peer_set] = true;
+				} else {
+					offers_main[*peer_set] = true;
+				}
+			}
+		}
+
+		let mut downgraded = PerPeerSet::default();
+		for peer_set in PeerSet::iter() {
+			downgraded[peer_set] = offers_other[peer_set] && !offers_main[peer_set];
+		}
+		downgraded
+	}
+
+	/// Compare the protocol names resolved by `self` and `other`.
+	pub fn diff(&self, other: &PeerSetProtocolNames) -> ProtocolNameDiff {
+		let mut diff = ProtocolNameDiff::default();
+		for (name, protocol) in &self.protocols {
+			match other.protocols.get(name) {
+				None => diff.removed.push(name.clone()),
+				Some(other_protocol) if other_protocol != protocol =>
+					diff.changed.push(name.clone()),
+				Some(_) => {},
+			}
+		}
+		diff.added = other
+			.protocols
+			.keys()
+			.filter(|name| !self.protocols.contains_key(*name))
+			.cloned()
+			.collect();
+
+		diff.added.sort();
+		diff.removed.sort();
+		diff.changed.sort();
+		diff
+	}
+
+	/// Convert into a [`CompactProtocolNames`], for cheaper lookups of on the wire names.
+	pub fn into_compact(self) -> CompactProtocolNames {
+		let mut protocols: Vec<_> = self.protocols.into_iter().collect();
+		protocols.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+		CompactProtocolNames { protocols }
+	}
+
+	/// A fingerprint of all resolved protocol names, along with the genesis hash, the
+	/// `/<genesis_hash>[/<fork_id>]` prefix, the main versions and the disabled fallbacks.
+	///
+	/// Instances constructed from the same inputs have the same fingerprint, which makes it easy
+	/// to compare the configurations of two nodes.
+	pub fn fingerprint(&self) -> [u8; 32] {
+		let mut entries: Vec<_> = self.protocols.iter().collect();
+		entries.sort();
+
+		let mut data = self.genesis_hash.as_bytes().to_vec();
+		// Length prefixes keep the encoding unambiguous.
+		data.extend_from_slice(&(self.prefix.len() as u32).to_le_bytes());
+		data.extend_from_slice(self.prefix.as_bytes());
+		for (name, (peer_set, version)) in entries {
+			data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+			data.extend_from_slice(name.as_bytes());
+			data.push(peer_set.index() as u8);
+			data.extend_from_slice(&version.to_le_bytes());
+		}
+		for peer_set in PeerSet::iter() {
+			data.extend_from_slice(&self.main_versions[peer_set].to_le_bytes());
+		}
+		let mut disabled: Vec<_> = self.disabled_fallbacks.iter().collect();
+		disabled.sort();
+		data.extend_from_slice(&(disabled.len() as u32).to_le_bytes());
+		for (peer_set, version) in disabled {
+			data.push(peer_set.index() as u8);
+			data.extend_from_slice(&version.to_le_bytes());
+		}
+		sp_core::hashing::blake2_256(&data)
+	}
+
+	/// The `/<genesis_hash>[/<fork_id>]` prefix shared by all generated names, e.g. to quickly
+	/// reject names of other chains.
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// Length of the `/<genesis_hash>[/<fork_id>]` prefix shared by all generated names.
+	pub fn prefix_len(&self) -> usize {
+		self.prefix.len()
+	}
+
+	/// The protocol name of this protocol based on `genesis_hash` and `fork_id`.
+	///
+	/// Prefer [`PeerSetProtocolNames::get_name`] if an instance is at hand, as it doesn't
+	/// hex encode the genesis hash on every call.
+	pub fn generate_name(
+		genesis_hash: &Hash,
+		fork_id: Option<&str>,
+		protocol: PeerSet,
+		version: ProtocolVersion,
+	) -> Cow<'static, str> {
+		Self::name_with_prefix(
+			&Self::name_prefix(genesis_hash, fork_id),
+			protocol.short_name(),
+			version,
+		)
+	}
+
+	/// The `/<genesis_hash>[/<fork_id>]` prefix of all protocol names.
+	///
+	/// An empty fork id is treated like no fork id, instead of adding an empty segment.
+	pub fn name_prefix(genesis_hash: &Hash, fork_id: Option<&str>) -> String {
+		match fork_id {
+			Some(fork_id) if !fork_id.is_empty() =>
+				format!("/{}/{}", hex::encode(genesis_hash), fork_id),
+			_ => format!("/{}", hex::encode(genesis_hash)),
+		}
+	}
+
+	/// The protocol name of a protocol with the given `short_name` under an already computed
+	/// `prefix`.
+	fn name_with_prefix(
+		prefix: &str,
+		short_name: &str,
+		version: ProtocolVersion,
+	) -> Cow<'static, str> {
+		format!("{}/{}/{}", prefix, short_name, version).into()
+	}
+}
+
+/// On the wire protocol name to [`PeerSet`] mapping, stored as a sorted list.
+///
+/// Trades a more expensive construction, see [`PeerSetProtocolNames::into_compact`], for a
+/// smaller memory footprint and cache friendlier lookups.
+#[derive(Debug, Clone)]
+pub struct CompactProtocolNames {
+	/// Sorted by name.
+	protocols: Vec<(Cow<'static, str>, (PeerSet, ProtocolVersion))>,
+}
+
+impl CompactProtocolNames {
+	/// Lookup the protocol using its on the wire name.
+	pub fn try_get_protocol(&self, name: &Cow<'static, str>) -> Option<(PeerSet, ProtocolVersion)> {
+		// Names come from the wire, don't bother comparing overly long ones.
+		if name.len() > MAX_PROTOCOL_NAME_LEN {
+			return None
+		}
+		self.protocols
+			.binary_search_by(|(registered, _)| registered.as_ref().cmp(name.as_ref()))
+			.ok()
+			.map(|index| self.protocols[index].1)
+	}
+}
+
+/// Builder of [`PeerSetProtocolNames`], see [`PeerSetProtocolNames::builder`].
+///
+/// By default, every peer set is registered at its default version, along with its legacy name.
+/// The genesis hash must always be set, see [`PeerSetProtocolNamesBuilder::genesis`].
+#[derive(Debug, Clone)]
+pub struct PeerSetProtocolNamesBuilder {
+	genesis_hash: Hash,
+	fork_id: Option<String>,
+	prefix: Option<String>,
+	with_legacy: bool,
+	strict: bool,
+	versions: HashMap<PeerSet, Vec<ProtocolVersion>>,
+	extra_versions: Vec<(PeerSet, ProtocolVersion)>,
+	short_name_override: PerPeerSet<Option<&'static str>>,
+	main_versions: PerPeerSet<Option<ProtocolVersion>>,
+	disabled_fallbacks: HashSet<(PeerSet, ProtocolVersion)>,
+}
+
+impl Default for PeerSetProtocolNamesBuilder {
+	fn default() -> Self {
+		Self {
+			genesis_hash: Hash::default(),
+			fork_id: None,
+			prefix: None,
+			with_legacy: true,
+			strict: false,
+			versions: HashMap::new(),
+			extra_versions: Vec::new(),
+			short_name_override: PerPeerSet::default(),
+			main_versions: PerPeerSet::default(),
+			disabled_fallbacks: HashSet::new(),
+		}
+	}
+}
+
+impl PeerSetProtocolNamesBuilder {
+	/// Set the genesis hash of the chain.
+	///
+	/// It defaults to zero, which [`PeerSetProtocolNamesBuilder::build`] rejects with
+	/// [`ProtocolNameError::ZeroGenesisHash`], even if a custom prefix is used.
+	pub fn genesis(mut self, genesis_hash: Hash) -> Self {
+		self.genesis_hash = genesis_hash;
+		self
+	}
+
+	/// Set the fork id of the chain, if any.
+	pub fn fork_id(mut self, fork_id: Option<String>) -> Self {
+		self.fork_id = fork_id;
+		self
+	}
+
+	/// Use a custom prefix instead of `/<genesis_hash>[/<fork_id>]` for all main names.
+	///
+	/// The prefix must start with a `/` and must not end with one.
+	pub fn prefix(mut self, prefix: String) -> Self {
+		self.prefix = Some(prefix);
+		self
+	}
+
+	/// Whether to resolve legacy protocol names (without genesis hash) as well.
+	pub fn with_legacy(mut self, with_legacy: bool) -> Self {
+		self.with_legacy = with_legacy;
+		self
+	}
+
+	/// Whether registering the same version of a peer set twice is an error, instead of a no-op.
+	pub fn strict(mut self, strict: bool) -> Self {
+		self.strict = strict;
+		self
+	}
+
+	/// Register `protocol` at exactly the given `versions`, which must include its default
+	/// version.
+	pub fn versions(mut self, protocol: PeerSet, versions: Vec<ProtocolVersion>) -> Self {
+		self.versions.insert(protocol, versions);
+		self
+	}
+
+	/// Use custom short names instead of [`PeerSet::short_name`] in main names, e.g. for chains
+	/// forking Polkadot. Legacy names are not affected.
+	pub fn short_name_override(mut self, overrides: PerPeerSet<Option<&'static str>>) -> Self {
+		self.short_name_override = overrides;
+		self
+	}
+
+	/// Additionally register `protocol` at `version`, next to its default version.
+	pub fn extra_version(mut self, protocol: PeerSet, version: ProtocolVersion) -> Self {
+		self.extra_versions.push((protocol, version));
+		self
+	}
+
+	/// Use `version` instead of the default version of `protocol` for its main name, e.g. to
+	/// advertise a new version while older ones become fallbacks.
+	///
+	/// The version must be registered, e.g. with [`PeerSetProtocolNamesBuilder::extra_version`].
+	pub fn main_version(mut self, protocol: PeerSet, version: ProtocolVersion) -> Self {
+		self.main_versions[protocol] = Some(version);
+		self
+	}
+
+	/// Stop accepting `protocol` at `version` as a fallback, e.g. during a forced upgrade. Its
+	/// names are still resolved, but not accepted, see [`PeerSetProtocolNames::is_accepted`].
+	///
+	/// The main version can't be disabled.
+	pub fn disable_fallback(mut self, protocol: PeerSet, version: ProtocolVersion) -> Self {
+		self.disabled_fallbacks.insert((protocol, version));
+		self
+	}
+
+	/// Build the [`PeerSetProtocolNames`], validating all names upfront.
+	pub fn build(self) -> Result<PeerSetProtocolNames, ProtocolNameError> {
+		if self.genesis_hash.is_zero() {
+			return Err(ProtocolNameError::ZeroGenesisHash)
+		}
+
+		let prefix = match self.prefix {
+			Some(prefix) if !prefix.starts_with('/') || prefix.ends_with('/') =>
+				return Err(ProtocolNameError::InvalidPrefix(prefix)),
+			Some(prefix) => prefix,
+			None => PeerSetProtocolNames::name_prefix(&self.genesis_hash, self.fork_id.as_deref()),
+		};
+
+		let short_names = PerPeerSet::new_with(|protocol| {
+			self.short_name_override[protocol].unwrap_or_else(|| protocol.short_name())
+		});
+		if let Some(short_name) = PeerSet::iter()
+			.map(|protocol| short_names[protocol])
+			.find(|short_name| short_name.is_empty() || short_name.contains('/'))
+		{
+			return Err(ProtocolNameError::InvalidShortName(short_name))
+		}
+
+		let main_versions = PerPeerSet::new_with(|protocol| {
+			self.main_versions[protocol].unwrap_or_else(|| protocol.get_default_version())
+		});
+		if let Some((protocol, version)) = PeerSet::iter()
+			.map(|protocol| (protocol, main_versions[protocol]))
+			.find(|main| self.disabled_fallbacks.contains(main))
+		{
+			return Err(ProtocolNameError::DisabledMainVersion(protocol, version))
+		}
+
+		let mut protocols = HashMap::new();
+		let mut names = HashMap::new();
+		for protocol in PeerSet::iter() {
+			let default_version = protocol.get_default_version();
+			let versions = self
+				.versions
+				.get(&protocol)
+				.cloned()
+				.unwrap_or_else(|| vec![default_version])
+				.into_iter()
+				.chain(
+					self.extra_versions
+						.iter()
+						.filter(|(extra_protocol, _)| *extra_protocol == protocol)
+						.map(|(_, version)| *version),
+				)
+				.collect::<Vec<_>>();
+			if !versions.contains(&default_version) {
+				return Err(ProtocolNameError::MissingDefaultVersion(protocol))
+			}
+			if let Some(main_version) = self.main_versions[protocol] {
+				if !versions.contains(&main_version) {
+					return Err(ProtocolNameError::MissingMainVersion(protocol, main_version))
+				}
+			}
+
+			for version in versions {
+				let name =
+					PeerSetProtocolNames::name_with_prefix(&prefix, short_names[protocol], version);
+				if names.insert((protocol, version), name.clone()).is_some() {
+					if self.strict {
+						return Err(ProtocolNameError::DuplicateVersion(protocol, version))
+					}
+					continue
+				}
+				PeerSetProtocolNames::try_insert_protocol(&mut protocols, name, protocol, version)?;
+				if !self.with_legacy {
+					continue
+				}
+				if let Some(legacy_name) = protocol.into_protocol_name(version) {
+					PeerSetProtocolNames::try_insert_protocol(
+						&mut protocols,
+						legacy_name,
+						protocol,
+						version,
+					)?;
+				}
+			}
+		}
+		Ok(PeerSetProtocolNames {
+			genesis_hash: self.genesis_hash,
+			fork_id: self.fork_id,
+			prefix,
+			protocols,
+			names,
+			short_names,
+			main_versions,
+			disabled_fallbacks: self.disabled_fallbacks,
+		})
+	}
+}
+
+/// Get the number of protocol names registered by all the given instances, e.g. of a node
+/// serving multiple chains.
+///
+/// Names are counted per instance, the same legacy name of two chains counts twice.
+pub fn total_registered_names(instances: &[PeerSetProtocolNames]) -> usize {
+	instances.iter().map(|names| names.protocols.len()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashSet;
+
+	#[test]
+	fn all_protocol_labels_are_unique() {
+		let labels = all_protocol_labels();
+
+		assert!(labels.contains(&"validation/1"));
+		assert!(labels.contains(&"collation/1"));
+		assert_eq!(labels.iter().collect::<HashSet<_>>().len(), labels.len());
+	}
+
+	#[test]
+	fn prefix_len_matches_generated_names() {
+		let genesis_hash = Hash::from([1; 32]);
+
+		let names = PeerSetProtocolNames::new(genesis_hash, None);
+		assert_eq!(names.prefix_len(), 65);
+		let name = names.get_main_name(PeerSet::Validation);
+		assert_eq!(&name[names.prefix_len()..], format!("/{}/1", PeerSet::Validation.short_name()));
+
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("test-fork"));
+		assert_eq!(names.prefix_len(), 75);
+		let name = names.get_main_name(PeerSet::Collation);
+		assert_eq!(&name[names.prefix_len()..], format!("/{}/1", PeerSet::Collation.short_name()));
+	}
+
+	#[test]
+	fn companion_req_resp_names_share_prefix() {
+		let genesis_hash = Hash::from([1; 32]);
+		let names = PeerSetProtocolNames::new(genesis_hash, None);
+		let prefix = format!("/{}/", hex::encode(genesis_hash));
+
+		let validation = PeerSet::Validation.companion_req_resp_names(&names);
+		assert_eq!(validation.len(), 2);
+		assert!(validation.iter().all(|name| name.starts_with(&prefix)));
+		assert!(validation[0].ends_with("/req_chunk/1"));
+		assert!(validation[1].ends_with("/req_available_data/1"));
+
+		let collation = PeerSet::Collation.companion_req_resp_names(&names);
+		assert_eq!(collation.len(), 1);
+		assert!(collation[0].starts_with(&prefix));
+		assert!(collation[0].ends_with("/req_pov/1"));
+	}
+
+	#[test]
+	fn companion_req_resp_names_match_req_protocol_names() {
+		let genesis_hash = Hash::from([1; 32]);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("test-fork"));
+		let req_protocol_names = ReqProtocolNames::new(genesis_hash, Some("test-fork"));
+
+		assert_eq!(
+			PeerSet::Validation.companion_req_resp_names(&names),
+			vec![
+				req_protocol_names.get_name(Protocol::ChunkFetchingV1),
+				req_protocol_names.get_name(Protocol::AvailableDataFetchingV1),
+			],
+		);
+		assert_eq!(
+			PeerSet::Collation.companion_req_resp_names(&names),
+			vec![req_protocol_names.get_name(Protocol::PoVFetchingV1)],
+		);
+	}
+
+	#[test]
+	fn main_names_are_served_from_cache() {
+		let genesis_hash = Hash::from([1; 32]);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("test-fork"));
+
+		for peer_set in PeerSet::iter() {
+			let version = peer_set.get_default_version();
+			let name = names.get_main_name(peer_set);
+
+			// Names are looked up from the ones generated in `new()`, nothing is re-encoded.
+			assert_eq!(Some(&name), names.names.get(&(peer_set, version)));
+			assert!(name.starts_with(&names.prefix));
+			assert_eq!(
+				name,
+				PeerSetProtocolNames::generate_name(
+					&genesis_hash,
+					Some("test-fork"),
+					peer_set,
+					version
+				),
+			);
+		}
+	}
+
+	fn assert_authority_inbound_open(config: &NonDefaultSetConfig) -> bool {
+		config.set_config.in_peers > 0 || !config.set_config.reserved_nodes.is_empty()
+	}
+
+	#[test]
+	fn get_info_registers_main_and_fallback_names() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), Some("fork"));
+
+		for peer_set in PeerSet::iter() {
+			let info = peer_set.get_info(IsAuthority::Yes, &names);
+			assert_eq!(info.notifications_protocol, names.get_main_name(peer_set));
+			assert_eq!(info.fallback_names, names.fallback_names(peer_set));
+			assert!(info.fallback_names.contains(&peer_set.into_default_protocol_name()));
+		}
+	}
+
+	#[test]
+	fn authorities_accept_inbound_connections() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		for peer_set in PeerSet::iter() {
+			let config = peer_set.get_info(IsAuthority::Yes, &names);
+			assert!(assert_authority_inbound_open(&config), "{:?} has no inbound slots", peer_set);
+		}
+	}
+
+	#[test]
+	fn additional_fork_names_are_resolved() {
+		let genesis_hash = Hash::from([1; 32]);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("old-fork"))
+			.with_additional_fork("new-fork")
+			.unwrap();
+
+		for peer_set in PeerSet::iter() {
+			let version = peer_set.get_default_version();
+			let old = PeerSetProtocolNames::generate_name(
+				&genesis_hash,
+				Some("old-fork"),
+				peer_set,
+				version,
+			);
+			let new = PeerSetProtocolNames::generate_name(
+				&genesis_hash,
+				Some("new-fork"),
+				peer_set,
+				version,
+			);
+
+			assert_eq!(names.try_get_protocol(&old), Some((peer_set, version)));
+			assert_eq!(names.try_get_protocol(&new), Some((peer_set, version)));
+			assert_eq!(names.get_main_name(peer_set), old);
+		}
+	}
+
+	#[test]
+	fn protocol_name_collisions_are_reported() {
+		let mut protocols = HashMap::new();
+		let name: Cow<'static, str> = "/polkadot/validation/1".into();
+
+		PeerSetProtocolNames::try_insert_protocol(
+			&mut protocols,
+			name.clone(),
+			PeerSet::Validation,
+			1,
+		)
+		.unwrap();
+		// Registering the same protocol again is fine.
+		PeerSetProtocolNames::try_insert_protocol(
+			&mut protocols,
+			name.clone(),
+			PeerSet::Validation,
+			1,
+		)
+		.unwrap();
+
+		assert_eq!(
+			PeerSetProtocolNames::try_insert_protocol(
+				&mut protocols,
+				name.clone(),
+				PeerSet::Collation,
+				1
+			),
+			Err(ProtocolNameCollision {
+				name,
+				protocol: PeerSet::Collation,
+				version: 1,
+				existing_protocol: PeerSet::Validation,
+				existing_version: 1,
+			}),
+		);
+	}
+
+	#[test]
+	fn authority_relevance() {
+		assert!(PeerSet::Validation.is_authority_relevant(IsAuthority::Yes));
+		assert!(PeerSet::Validation.is_authority_relevant(IsAuthority::No));
+		assert!(PeerSet::Collation.is_authority_relevant(IsAuthority::Yes));
+		assert!(!PeerSet::Collation.is_authority_relevant(IsAuthority::No));
+	}
+
+	#[test]
+	fn slot_summary_matches_defaults() {
+		let summary = slot_summary(IsAuthority::Yes);
+		assert_eq!(summary[PeerSet::Validation], (11, 11));
+		assert_eq!(summary[PeerSet::Collation], (100, 0));
+
+		let summary = slot_summary(IsAuthority::No);
+		assert_eq!(summary[PeerSet::Validation], (11, 11));
+		assert_eq!(summary[PeerSet::Collation], (0, 0));
+	}
+
+	#[test]
+	fn collation_authority_in_peers_defaults_to_100() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let info = PeerSet::Collation.get_info(IsAuthority::Yes, &names);
+		assert_eq!(info.set_config.in_peers, 100);
+	}
+
+	#[test]
+	fn collation_authority_in_peers_can_be_overridden() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let config = PeerSetConfig { collation_authority_in_peers: 250, ..Default::default() };
+
+		let info = PeerSet::Collation.get_info_with_config(IsAuthority::Yes, &config, &names);
+		assert_eq!(info.set_config.in_peers, 250);
+		let info = PeerSet::Collation.get_info_with_config(IsAuthority::No, &config, &names);
+		assert_eq!(info.set_config.in_peers, 0);
+	}
+
+	#[test]
+	fn authorities_need_collation_in_peers() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let config = PeerSetConfig { collation_authority_in_peers: 0, ..Default::default() };
+
+		assert_eq!(
+			peer_sets_info_with_config(IsAuthority::Yes, &config, &names).unwrap_err(),
+			InvalidPeerSetConfig::NoCollationAuthorityInPeers,
+		);
+		assert!(peer_sets_info_with_config(IsAuthority::No, &config, &names).is_ok());
+	}
+
+	#[test]
+	fn diff_of_fork_id_change() {
+		let genesis_hash = Hash::from([1; 32]);
+		let old = PeerSetProtocolNames::new(genesis_hash, None);
+		let new = PeerSetProtocolNames::new(genesis_hash, Some("x"));
+
+		let diff = old.diff(&new);
+
+		let mut added: Vec<_> = PeerSet::iter().map(|s| new.get_main_name(s)).collect();
+		added.sort();
+		let mut removed: Vec<_> = PeerSet::iter().map(|s| old.get_main_name(s)).collect();
+		removed.sort();
+		assert_eq!(diff, ProtocolNameDiff { added, removed, changed: Vec::new() });
+
+		// Legacy names are the same on both sides.
+		for peer_set in PeerSet::iter() {
+			let legacy_name = peer_set.into_default_protocol_name();
+			assert!(!diff.added.contains(&legacy_name));
+			assert!(!diff.removed.contains(&legacy_name));
+		}
+
+		assert_eq!(old.diff(&old), ProtocolNameDiff::default());
+	}
+
+	#[test]
+	fn overly_long_protocol_names_are_rejected() {
+		let names = PeerSetProtocolNames::new(Hash::from([1; 32]), None);
+
+		let long_name: Cow<'static, str> = "/polkadot/validation/1".repeat(500).into();
+		assert!(long_name.len() > 10 * 1024);
+		assert_eq!(names.try_get_protocol(&long_name), None);
+
+		for peer_set in PeerSet::iter() {
+			assert!(names.get_main_name(peer_set).len() <= MAX_PROTOCOL_NAME_LEN);
+		}
+	}
+
+	#[test]
+	fn legacy_only_offers_are_downgrades() {
+		let names = PeerSetProtocolNames::new(Hash::from([1; 32]), None);
+		let legacy_validation = PeerSet::Validation.get_default_protocol_name().to_owned();
+		let main_validation = names.get_main_name(PeerSet::Validation).into_owned();
+		let main_collation = names.get_main_name(PeerSet::Collation).into_owned();
+
+		let downgraded = names.is_forced_downgrade(&[legacy_validation.clone(), main_collation]);
+		assert!(downgraded[PeerSet::Validation]);
+		assert!(!downgraded[PeerSet::Collation]);
+
+		let downgraded = names.is_forced_downgrade(&[legacy_validation, main_validation]);
+		assert!(!downgraded[PeerSet::Validation]);
+		assert!(!downgraded[PeerSet::Collation]);
+	}
+
+	#[test]
+	fn main_version_offers_of_an_additional_fork_are_no_downgrades() {
+		let genesis_hash = Hash::from([1; 32]);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("old-fork"))
+			.with_additional_fork("new-fork")
+			.unwrap();
+		let new_fork = PeerSetProtocolNames::new(genesis_hash, Some("new-fork"));
+		let offered: Vec<_> = PeerSet::iter()
+			.map(|peer_set| new_fork.get_main_name(peer_set).into_owned())
+			.collect();
+
+		let downgraded = names.is_forced_downgrade(&offered);
+		for peer_set in PeerSet::iter() {
+			assert!(!downgraded[peer_set], "{:?}", peer_set);
+		}
+
+		let legacy_validation = PeerSet::Validation.get_default_protocol_name().to_owned();
+		assert!(names.is_forced_downgrade(&[legacy_validation])[PeerSet::Validation]);
+	}
+
+	#[test]
+	fn peer_set_indices_match_iteration_order() {
+		assert_eq!(PeerSet::COUNT, PeerSet::iter().count());
+
+		for (index, peer_set) in PeerSet::iter().enumerate() {
+			assert_eq!(peer_set.index(), index);
+			assert_eq!(PeerSet::from_index(peer_set.index()), Some(peer_set));
+		}
+		assert_eq!(PeerSet::from_index(PeerSet::COUNT), None);
+	}
+
+	#[test]
+	fn validation_is_not_enabled_pre_sync() {
+		assert_eq!(peer_sets_pre_sync(), vec![PeerSet::Collation]);
+	}
+
+	#[test]
+	fn total_registered_names_sums_instances() {
+		let a = PeerSetProtocolNames::new(Hash::from([1; 32]), None);
+		let b = PeerSetProtocolNames::new(Hash::from([2; 32]), Some("fork"));
+
+		// A main and a legacy name per peer set.
+		assert_eq!(total_registered_names(std::slice::from_ref(&a)), 2 * PeerSet::COUNT);
+		assert_eq!(total_registered_names(&[a, b]), 4 * PeerSet::COUNT);
+		assert_eq!(total_registered_names(&[]), 0);
+	}
+
+	#[test]
+	fn validation_slots_do_not_underflow() {
+		assert_eq!(validation_slots_for(0), (0, 0));
+		assert_eq!(validation_slots_for(1), (0, 0));
+		assert_eq!(validation_slots_for(2), (0, 0));
+		assert_eq!(validation_slots_for(25), (11, 11));
+
+		assert_eq!(validation_slots(), validation_slots_for(crate::MIN_GOSSIP_PEERS));
+	}
+
+	#[test]
+	fn builder_registers_extra_versions() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let names = PeerSetProtocolNames::builder()
+			.genesis(genesis_hash)
+			.fork_id(Some("fork".to_owned()))
+			.extra_version(PeerSet::Validation, 2)
+			.build()
+			.unwrap();
+
+		let extra_name = PeerSetProtocolNames::generate_name(
+			&genesis_hash,
+			Some("fork"),
+			PeerSet::Validation,
+			2,
+		);
+		assert_eq!(names.try_get_protocol(&extra_name), Some((PeerSet::Validation, 2)));
+		for peer_set in PeerSet::iter() {
+			let version = peer_set.get_default_version();
+			let name = names.get_main_name(peer_set);
+			assert_eq!(names.try_get_protocol(&name), Some((peer_set, version)));
+			let legacy_name = peer_set.into_default_protocol_name();
+			assert_eq!(names.try_get_protocol(&legacy_name), Some((peer_set, version)));
+		}
+	}
+
+	#[test]
+	fn builder_can_skip_legacy_names() {
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.with_legacy(false)
+			.build()
+			.unwrap();
+
+		for peer_set in PeerSet::iter() {
+			assert_eq!(names.try_get_protocol(&peer_set.into_default_protocol_name()), None);
+		}
+	}
+
+	#[test]
+	fn reputation_deltas_per_peer_set() {
+		let value = |rep: Rep| rep.into_base_rep().value;
+
+		for peer_set in PeerSet::iter() {
+			let deltas = peer_set.reputation_deltas();
+			assert!(deltas.good_message.is_benefit());
+			assert!(value(deltas.good_message) > 0);
+			assert!(value(deltas.bad_message) < value(deltas.timeout));
+			assert!(value(deltas.timeout) < 0);
+		}
+
+		let validation = PeerSet::Validation.reputation_deltas();
+		let collation = PeerSet::Collation.reputation_deltas();
+		assert!(value(collation.good_message) > value(validation.good_message));
+		assert!(value(collation.bad_message) < value(validation.bad_message));
+		assert!(value(collation.timeout) < value(validation.timeout));
+	}
+
+	#[test]
+	fn try_get_protocol_full_reports_fork_id() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("old"))
+			.with_additional_fork("new")
+			.unwrap();
+
+		for fork_id in ["old", "new"] {
+			let name = PeerSetProtocolNames::generate_name(
+				&genesis_hash,
+				Some(fork_id),
+				PeerSet::Validation,
+				1,
+			);
+			assert_eq!(
+				names.try_get_protocol_full(&name),
+				Some(ResolvedProtocol {
+					peer_set: PeerSet::Validation,
+					version: 1,
+					fork_id: Some(fork_id.to_owned()),
+				}),
+			);
+		}
+
+		let legacy_name = PeerSet::Collation.into_default_protocol_name();
+		assert_eq!(
+			names.try_get_protocol_full(&legacy_name),
+			Some(ResolvedProtocol { peer_set: PeerSet::Collation, version: 1, fork_id: None }),
+		);
+
+		let without_fork_id = PeerSetProtocolNames::new(genesis_hash, None);
+		let name = without_fork_id.get_main_name(PeerSet::Collation);
+		assert_eq!(without_fork_id.try_get_protocol_full(&name).unwrap().fork_id, None);
+		assert_eq!(names.try_get_protocol_full(&name), None);
+	}
+
+	#[test]
+	fn peer_sets_info_is_in_registration_order() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		assert!(PeerSet::Validation < PeerSet::Collation);
+
+		let mut peer_sets: Vec<_> = PeerSet::iter().collect();
+		peer_sets.sort();
+		for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+			let protocols: Vec<_> = peer_sets_info(is_authority, &names)
+				.into_iter()
+				.map(|config| config.notifications_protocol)
+				.collect();
+			let expected: Vec<_> =
+				peer_sets.iter().map(|peer_set| names.get_main_name(*peer_set)).collect();
+			assert_eq!(protocols, expected);
+		}
+	}
+
+	#[test]
+	fn builder_registers_multiple_versions_under_custom_prefix() {
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.prefix("/custom".to_owned())
+			.versions(PeerSet::Validation, vec![1, 2, 3])
+			.with_legacy(false)
+			.build()
+			.unwrap();
+
+		for version in 1..=3 {
+			let name = format!("/custom/{}/{}", PeerSet::Validation.short_name(), version).into();
+			assert_eq!(names.try_get_protocol(&name), Some((PeerSet::Validation, version)));
+		}
+		assert_eq!(
+			names.get_main_name(PeerSet::Collation),
+			format!("/custom/{}/1", PeerSet::Collation.short_name()),
+		);
+		assert_eq!(names.prefix_len(), "/custom".len());
+	}
+
+	#[test]
+	fn builder_rejects_invalid_configurations() {
+		for prefix in ["custom", "/custom/", ""] {
+			assert_eq!(
+				PeerSetProtocolNames::builder()
+					.genesis(Hash::repeat_byte(0x42))
+					.prefix(prefix.to_owned())
+					.build()
+					.unwrap_err(),
+				ProtocolNameError::InvalidPrefix(prefix.to_owned()),
+			);
+		}
+
+		assert_eq!(
+			PeerSetProtocolNames::builder()
+				.genesis(Hash::repeat_byte(0x42))
+				.versions(PeerSet::Collation, vec![2])
+				.build()
+				.unwrap_err(),
+			ProtocolNameError::MissingDefaultVersion(PeerSet::Collation),
+		);
+
+		// Registering the same version twice only fails in strict mode.
+		let builder = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.versions(PeerSet::Validation, vec![1, 2])
+			.extra_version(PeerSet::Validation, 2);
+		assert!(builder.clone().build().is_ok());
+		assert_eq!(
+			builder.strict(true).build().unwrap_err(),
+			ProtocolNameError::DuplicateVersion(PeerSet::Validation, 2),
+		);
+	}
+
+	#[test]
+	fn notification_memory_upper_bound_of_validation() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let (in_peers, out_peers) = validation_slots();
+		let bound = PeerSet::Validation.get_max_notification_size(IsAuthority::Yes) *
+			(in_peers as u64 + out_peers as u64);
+
+		assert_eq!(
+			PeerSet::Validation.notification_memory_upper_bound(IsAuthority::Yes, &names),
+			bound
+		);
+		assert_eq!(
+			PeerSet::Validation.notification_memory_upper_bound(IsAuthority::No, &names),
+			bound
+		);
+		assert_eq!(PeerSet::Collation.notification_memory_upper_bound(IsAuthority::No, &names), 0);
+	}
+
+	#[test]
+	fn per_peer_set_new_with_follows_iteration_order() {
+		let mut calls = Vec::new();
+		let labels = PerPeerSet::new_with(|peer_set| {
+			calls.push(peer_set);
+			peer_set.get_protocol_label(peer_set.get_default_version()).unwrap().to_string()
+		});
+
+		assert_eq!(calls, PeerSet::iter().collect::<Vec<_>>());
+		assert_eq!(labels[PeerSet::Validation], "validation/1");
+		assert_eq!(labels[PeerSet::Collation], "collation/1");
+	}
+
+	#[test]
+	fn every_peer_set_has_a_default_protocol_label() {
+		for peer_set in PeerSet::iter() {
+			assert!(
+				peer_set.get_protocol_label(peer_set.get_default_version()).is_some(),
+				"{:?} has no protocol label",
+				peer_set,
+			);
+		}
+	}
+
+	#[test]
+	fn names_and_labels_use_the_short_name() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+
+		for peer_set in PeerSet::iter() {
+			let version = peer_set.get_default_version();
+			for fork_id in [None, Some("fork")] {
+				let name =
+					PeerSetProtocolNames::generate_name(&genesis_hash, fork_id, peer_set, version);
+				assert!(name.split('/').any(|segment| segment == peer_set.short_name()));
+			}
+
+			assert_eq!(
+				peer_set.get_protocol_label(version),
+				Some(format!("{}/{}", peer_set.short_name(), version).as_str()),
+			);
+		}
+	}
+
+	#[test]
+	fn try_get_name_only_succeeds_for_supported_versions() {
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.extra_version(PeerSet::Validation, 2)
+			.build()
+			.unwrap();
+
+		assert_eq!(
+			names.try_get_name(PeerSet::Validation, 1),
+			Ok(names.get_name(PeerSet::Validation, 1))
+		);
+		assert_eq!(
+			names.try_get_name(PeerSet::Validation, 2),
+			Ok(names.get_name(PeerSet::Validation, 2))
+		);
+		assert_eq!(
+			names.try_get_name(PeerSet::Collation, 2),
+			Err(UnsupportedVersion { protocol: PeerSet::Collation, version: 2 }),
+		);
+	}
+
+	#[test]
+	fn messaging_style_per_peer_set() {
+		assert_eq!(PeerSet::Validation.messaging_style(), MessagingStyle::Gossip);
+		assert_eq!(PeerSet::Collation.messaging_style(), MessagingStyle::Directed);
+	}
+
+	#[test]
+	fn add_peer_sets_registers_every_peer_set() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let mut config = NetworkConfiguration::new_local();
+		let existing = config.extra_sets.len();
+
+		assert_eq!(add_peer_sets(&mut config, IsAuthority::Yes, &names), PeerSet::COUNT);
+		assert_eq!(config.extra_sets.len(), existing + PeerSet::COUNT);
+	}
+
+	#[test]
+	fn try_get_protocol_with_reason_classifies_misses() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("fork"));
+		let name = |genesis_hash, fork_id, version| {
+			PeerSetProtocolNames::generate_name(
+				&genesis_hash,
+				fork_id,
+				PeerSet::Validation,
+				version,
+			)
+		};
+
+		assert_eq!(
+			names.try_get_protocol_with_reason(&name(genesis_hash, Some("fork"), 1)),
+			Ok((PeerSet::Validation, 1)),
+		);
+		assert_eq!(
+			names.try_get_protocol_with_reason(&name(Hash::repeat_byte(0x43), Some("fork"), 1)),
+			Err(LookupMiss::WrongGenesis),
+		);
+		assert_eq!(
+			names.try_get_protocol_with_reason(&name(genesis_hash, Some("other"), 1)),
+			Err(LookupMiss::WrongFork),
+		);
+		assert_eq!(
+			names.try_get_protocol_with_reason(&name(genesis_hash, None, 1)),
+			Err(LookupMiss::WrongFork),
+		);
+		assert_eq!(
+			names.try_get_protocol_with_reason(&name(genesis_hash, Some("fork"), 2)),
+			Err(LookupMiss::UnsupportedVersion(PeerSet::Validation, 2)),
+		);
+
+		for unknown in [
+			"/polkadot/validation/2".to_owned(),
+			format!("/{}/fork/gossip/1", hex::encode(genesis_hash)),
+			format!("/{}/fork/validation/one", hex::encode(genesis_hash)),
+			format!("/{}/fork/extra/validation/1", hex::encode(genesis_hash)),
+			"/0042/validation/1".to_owned(),
+		] {
+			assert_eq!(
+				names.try_get_protocol_with_reason(&unknown.into()),
+				Err(LookupMiss::UnknownShape),
+			);
+		}
+	}
+
+	#[test]
+	fn supported_versions_per_peer_set() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		for peer_set in PeerSet::iter() {
+			assert_eq!(peer_set.min_supported_version(&names), 1);
+			assert_eq!(peer_set.max_supported_version(&names), 1);
+			assert_eq!(names.supported_versions(peer_set), vec![1]);
+		}
+
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.extra_version(PeerSet::Validation, 2)
+			.build()
+			.unwrap();
+		assert_eq!(names.supported_versions(PeerSet::Validation), vec![1, 2]);
+		assert_eq!(names.supported_versions(PeerSet::Collation), vec![1]);
+		assert_eq!(PeerSet::Validation.min_supported_version(&names), 1);
+		assert_eq!(PeerSet::Validation.max_supported_version(&names), 2);
+		assert_eq!(PeerSet::Collation.max_supported_version(&names), 1);
+
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.extra_version(PeerSet::Validation, 3)
+			.build()
+			.unwrap();
+		assert_eq!(names.supported_versions(PeerSet::Validation), vec![1, 3]);
+	}
+
+	#[test]
+	fn fingerprint_depends_on_inputs_only() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let fingerprint = PeerSetProtocolNames::new(genesis_hash, Some("fork")).fingerprint();
+
+		assert_eq!(
+			PeerSetProtocolNames::new(genesis_hash, Some("fork")).fingerprint(),
+			fingerprint
+		);
+		assert_ne!(
+			PeerSetProtocolNames::new(genesis_hash, Some("other")).fingerprint(),
+			fingerprint
+		);
+		assert_ne!(PeerSetProtocolNames::new(genesis_hash, None).fingerprint(), fingerprint);
+		assert_ne!(
+			PeerSetProtocolNames::new(Hash::repeat_byte(0x43), Some("fork")).fingerprint(),
+			fingerprint,
+		);
+	}
+
+	#[test]
+	fn fingerprint_covers_main_versions_and_disabled_fallbacks() {
+		let builder = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.extra_version(PeerSet::Validation, 2);
+		let fingerprint = builder.clone().build().unwrap().fingerprint();
+
+		let upgraded = builder.clone().main_version(PeerSet::Validation, 2);
+		assert_ne!(upgraded.clone().build().unwrap().fingerprint(), fingerprint);
+		assert_ne!(
+			upgraded
+				.clone()
+				.disable_fallback(PeerSet::Validation, 1)
+				.build()
+				.unwrap()
+				.fingerprint(),
+			upgraded.build().unwrap().fingerprint(),
+		);
+	}
+
+	#[test]
+	fn reduced_gossip_halves_non_authority_validation_slots() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let reduced =
+			PeerSetConfig { gossip_slot_mode: GossipSlotMode::ReducedGossip, ..Default::default() };
+		let (in_peers, out_peers) = validation_slots();
+
+		let info = PeerSet::Validation.get_info_with_config(IsAuthority::No, &reduced, &names);
+		assert_eq!(info.set_config.in_peers, in_peers / 2);
+		assert_eq!(info.set_config.out_peers, out_peers / 2);
+		let info =
+			PeerSet::Validation.get_info_with_config(IsAuthority::No, &Default::default(), &names);
+		assert_eq!(info.set_config.in_peers, in_peers);
+		assert_eq!(info.set_config.out_peers, out_peers);
+
+		let info = PeerSet::Validation.get_info_with_config(IsAuthority::Yes, &reduced, &names);
+		assert_eq!(info.set_config.in_peers, in_peers);
+		assert_eq!(info.set_config.out_peers, out_peers);
+	}
+
+	#[test]
+	fn compact_protocol_names_lookup_parity() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), Some("fork"))
+			.with_additional_fork("other")
+			.unwrap();
+		let compact = names.clone().into_compact();
+
+		let unknown: Cow<'static, str> = "/polkadot/unknown/1".into();
+		for name in names.protocols.keys().chain(std::iter::once(&unknown)) {
+			assert_eq!(compact.try_get_protocol(name), names.try_get_protocol(name));
+		}
+		assert_eq!(compact.try_get_protocol(&unknown), None);
+	}
+
+	#[test]
+	fn only_legacy_names_are_legacy() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+
+		assert!(names.is_legacy_name(&"/polkadot/validation/1".into()));
+		assert!(names.is_legacy_name(&"/polkadot/collation/1".into()));
+		for peer_set in PeerSet::iter() {
+			assert!(!names.is_legacy_name(&names.get_main_name(peer_set)));
+		}
+		assert!(!names.is_legacy_name(&"/polkadot/validation/2".into()));
+
+		// Names which don't resolve legacy names don't consider them legacy either.
+		let names = PeerSetProtocolNames::main_only(Hash::repeat_byte(0x42), None);
+		assert!(!names.is_legacy_name(&"/polkadot/validation/1".into()));
+		assert!(!names.is_legacy_name(&"/polkadot/collation/1".into()));
+	}
+
+	#[test]
+	fn about_to_be_authority_gossips_but_takes_no_collations() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let roles = [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority];
+		let reduced =
+			PeerSetConfig { gossip_slot_mode: GossipSlotMode::ReducedGossip, ..Default::default() };
+
+		let full_gossip =
+			PeerSet::Validation.get_info_with_config(IsAuthority::Yes, &reduced, &names);
+		let info = PeerSet::Validation.get_info_with_config(
+			IsAuthority::AboutToBeAuthority,
+			&reduced,
+			&names,
+		);
+		assert_eq!(info.set_config.in_peers, full_gossip.set_config.in_peers);
+		assert_eq!(info.set_config.out_peers, full_gossip.set_config.out_peers);
+
+		for is_authority in roles {
+			let info = PeerSet::Validation.get_info(is_authority, &names);
+			assert_eq!(
+				info.set_config.non_reserved_mode,
+				sc_network::config::NonReservedPeerMode::Accept
+			);
+
+			let info = PeerSet::Collation.get_info(is_authority, &names);
+			let accepts_collations = is_authority == IsAuthority::Yes;
+			assert_eq!(info.set_config.in_peers > 0, accepts_collations);
+			assert_eq!(
+				info.set_config.non_reserved_mode ==
+					sc_network::config::NonReservedPeerMode::Accept,
+				accepts_collations,
+			);
+		}
+	}
+
+	#[test]
+	fn describe_config_matches_get_info() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		for peer_set in PeerSet::iter() {
+			for is_authority in [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority]
+			{
+				let info = peer_set.get_info(is_authority, &names);
+				let summary = peer_set.describe_config(is_authority);
+
+				assert_eq!(summary.peer_set, peer_set);
+				assert_eq!(summary.is_authority, is_authority);
+				assert_eq!(summary.in_peers, info.set_config.in_peers);
+				assert_eq!(summary.out_peers, info.set_config.out_peers);
+				assert_eq!(summary.non_reserved_mode, info.set_config.non_reserved_mode);
+				assert_eq!(summary.max_notification_size, info.max_notification_size);
+			}
+		}
+	}
+
+	#[test]
+	fn default_version_range_of_peer_sets() {
+		for peer_set in PeerSet::iter() {
+			assert_eq!(peer_set.default_version_range().collect::<Vec<_>>(), vec![1]);
+		}
+	}
+
+	#[test]
+	fn protocol_id_is_the_main_name() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), Some("fork"));
+
+		for peer_set in PeerSet::iter() {
+			assert_eq!(peer_set.protocol_id(&names).as_ref(), names.get_main_name(peer_set));
+		}
+	}
+
+	#[test]
+	fn collation_can_be_disabled() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let mut config = PeerSetConfig { collation_authority_in_peers: 0, ..Default::default() };
+		config.enabled[PeerSet::Collation] = false;
+
+		for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+			let infos = peer_sets_info_with_config(is_authority, &config, &names).unwrap();
+			assert_eq!(infos.len(), 1);
+			assert_eq!(infos[0].notifications_protocol, names.get_main_name(PeerSet::Validation));
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn peer_sets_info_json_describes_validation() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let json = peer_sets_info_json(IsAuthority::Yes, &names);
+
+		let validation = &json[0];
+		let info = PeerSet::Validation.get_info(IsAuthority::Yes, &names);
+		assert_eq!(validation["name"], info.notifications_protocol.as_ref());
+		assert_eq!(validation["fallback_names"], serde_json::json!(info.fallback_names));
+		assert_eq!(validation["in_peers"], validation_slots().0);
+		assert_eq!(json.as_array().unwrap().len(), PeerSet::COUNT);
+	}
+
+	#[test]
+	fn same_peer_set_of_names() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let main_validation = names.get_main_name(PeerSet::Validation);
+		let legacy_validation = PeerSet::Validation.into_default_protocol_name();
+		let main_collation = names.get_main_name(PeerSet::Collation);
+		let unknown = "/polkadot/unknown/1".into();
+
+		assert!(names.same_peer_set(&main_validation, &legacy_validation));
+		assert!(names.same_peer_set(&main_collation, &main_collation));
+		assert!(!names.same_peer_set(&main_validation, &main_collation));
+		assert!(!names.same_peer_set(&unknown, &unknown));
+	}
+
+	#[test]
+	fn idle_timeouts_propagate_into_the_summary() {
+		assert!(
+			PeerSet::Validation.describe_config(IsAuthority::Yes).idle_timeout >
+				PeerSet::Collation.describe_config(IsAuthority::Yes).idle_timeout
+		);
+
+		let mut config = PeerSetConfig::default();
+		config.idle_timeouts[PeerSet::Collation] = Duration::from_secs(5);
+		for peer_set in PeerSet::iter() {
+			let summary = peer_set.describe_config_with_config(IsAuthority::Yes, &config);
+			assert_eq!(summary.idle_timeout, config.idle_timeouts[peer_set]);
+		}
+	}
+
+	#[test]
+	fn new_with_versions_registers_exactly_the_given_versions() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let versions = PerPeerSet::new_with(|peer_set| match peer_set {
+			PeerSet::Validation => vec![1, 2],
+			PeerSet::Collation => vec![1],
+		});
+		let names =
+			PeerSetProtocolNames::new_with_versions(genesis_hash, Some("fork"), &versions).unwrap();
+
+		for peer_set in PeerSet::iter() {
+			for version in 1..=2 {
+				let name = PeerSetProtocolNames::generate_name(
+					&genesis_hash,
+					Some("fork"),
+					peer_set,
+					version,
+				);
+				let expected = versions[peer_set].contains(&version).then_some((peer_set, version));
+				assert_eq!(names.try_get_protocol(&name), expected);
+			}
+			let legacy_name = peer_set.into_default_protocol_name();
+			assert_eq!(names.try_get_protocol(&legacy_name), Some((peer_set, 1)));
+		}
+	}
+
+	#[test]
+	fn try_get_protocol_str_lookups() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let main_name = names.get_main_name(PeerSet::Collation);
+
+		assert_eq!(names.try_get_protocol_str(&main_name), Some((PeerSet::Collation, 1)));
+		assert_eq!(
+			names.try_get_protocol_str("/polkadot/validation/1"),
+			Some((PeerSet::Validation, 1))
+		);
+		assert_eq!(names.try_get_protocol_str("/polkadot/unknown/1"), None);
+	}
+
+	#[test]
+	fn authorities_take_part_in_all_peer_sets() {
+		assert_eq!(
+			PeerSet::authority_peer_sets().collect::<Vec<_>>(),
+			vec![PeerSet::Validation, PeerSet::Collation],
+		);
+	}
+
+	#[test]
+	fn only_equal_versions_are_compatible() {
+		for peer_set in PeerSet::iter() {
+			assert!(peer_set.is_version_compatible(1, 1));
+			assert!(peer_set.is_version_compatible(2, 2));
+			assert!(!peer_set.is_version_compatible(1, 2));
+			assert!(!peer_set.is_version_compatible(2, 1));
+		}
+	}
+
+	#[test]
+	fn negotiated_name_picks_the_highest_common_version() {
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.versions(PeerSet::Validation, vec![1, 2, 3])
+			.build()
+			.unwrap();
+		let name = |version| Some(names.get_name(PeerSet::Validation, version));
+
+		assert_eq!(names.negotiated_name(PeerSet::Validation, &[1, 2, 3]), name(3));
+		assert_eq!(names.negotiated_name(PeerSet::Validation, &[2, 1]), name(2));
+		assert_eq!(names.negotiated_name(PeerSet::Validation, &[2, 4]), name(2));
+		assert_eq!(names.negotiated_name(PeerSet::Validation, &[4, 5]), None);
+		assert_eq!(names.negotiated_name(PeerSet::Validation, &[]), None);
+		assert_eq!(names.negotiated_name(PeerSet::Collation, &[2, 3]), None);
+	}
+
+	#[test]
+	fn negotiated_name_skips_disabled_fallbacks() {
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.versions(PeerSet::Validation, vec![1, 2, 3])
+			.main_version(PeerSet::Validation, 3)
+			.disable_fallback(PeerSet::Validation, 2)
+			.build()
+			.unwrap();
+
+		assert_eq!(names.negotiated_name(PeerSet::Validation, &[2]), None);
+		assert_eq!(
+			names.negotiated_name(PeerSet::Validation, &[1, 2]),
+			Some(names.get_name(PeerSet::Validation, 1)),
+		);
+	}
+
+	#[test]
+	fn per_peer_set_checked_index() {
+		let mut per_peer_set = PerPeerSet::new_with(|peer_set| peer_set.index());
+		*per_peer_set.get_mut(PeerSet::Collation) += 10;
+
+		assert_eq!(per_peer_set.checked_index(PeerSet::Validation.index()), Some(&0));
+		assert_eq!(per_peer_set.checked_index(PeerSet::Collation.index()), Some(&11));
+		assert_eq!(per_peer_set.get(PeerSet::Collation), &11);
+		assert_eq!(per_peer_set.checked_index(PeerSet::COUNT), None);
+		assert_eq!(per_peer_set.checked_index(usize::MAX), None);
+	}
+
+	#[test]
+	fn short_names_can_be_overridden() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let overrides = PerPeerSet::new_with(|peer_set| match peer_set {
+			PeerSet::Validation => Some("para-validation"),
+			PeerSet::Collation => None,
+		});
+		let names = PeerSetProtocolNames::builder()
+			.genesis(genesis_hash)
+			.short_name_override(overrides)
+			.build()
+			.unwrap();
+
+		let prefix = format!("/{}", hex::encode(genesis_hash));
+		let validation = names.get_main_name(PeerSet::Validation);
+		assert_eq!(validation, format!("{}/para-validation/1", prefix));
+		assert_eq!(names.try_get_protocol(&validation), Some((PeerSet::Validation, 1)));
+		assert_eq!(names.get_main_name(PeerSet::Collation), format!("{}/collation/1", prefix));
+		// Legacy names are untouched.
+		assert_eq!(
+			names.try_get_protocol(&PeerSet::Validation.into_default_protocol_name()),
+			Some((PeerSet::Validation, 1)),
+		);
+
+		let invalid = PerPeerSet::new_with(|_| Some("para/validation"));
+		assert_eq!(
+			PeerSetProtocolNames::builder()
+				.genesis(Hash::repeat_byte(0x42))
+				.short_name_override(invalid)
+				.build()
+				.unwrap_err(),
+			ProtocolNameError::InvalidShortName("para/validation"),
+		);
+	}
+
+	#[test]
+	fn builder_rejects_colliding_short_names() {
+		let genesis_hash = Hash::repeat_byte(0x42);
+		let overrides = PerPeerSet::new_with(|peer_set| match peer_set {
+			PeerSet::Validation => Some("collation"),
+			PeerSet::Collation => None,
+		});
+
+		assert_eq!(
+			PeerSetProtocolNames::builder()
+				.genesis(genesis_hash)
+				.short_name_override(overrides)
+				.build()
+				.unwrap_err(),
+			ProtocolNameError::Collision(ProtocolNameCollision {
+				name: format!("/{}/collation/1", hex::encode(genesis_hash)).into(),
+				protocol: PeerSet::Collation,
+				version: 1,
+				existing_protocol: PeerSet::Validation,
+				existing_version: 1,
+			}),
+		);
+	}
+
+	#[test]
+	fn every_role_registers_all_peer_sets() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let config = PeerSetConfig::default();
+		for is_authority in [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority] {
+			assert_eq!(
+				peer_sets_to_register(is_authority, &config),
+				vec![PeerSet::Validation, PeerSet::Collation],
+			);
+			assert_eq!(
+				peer_sets_info(is_authority, &names).len(),
+				peer_sets_to_register(is_authority, &config).len()
+			);
+		}
+	}
+
+	#[test]
+	fn disabled_peer_sets_are_not_registered() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let mut config = PeerSetConfig::default();
+		config.enabled[PeerSet::Collation] = false;
+		for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+			assert_eq!(peer_sets_to_register(is_authority, &config), vec![PeerSet::Validation]);
+		}
+		assert_eq!(
+			peer_sets_info_with_config(IsAuthority::No, &config, &names)
+				.unwrap()
+				.into_iter()
+				.map(|info| info.notifications_protocol)
+				.collect::<Vec<_>>(),
+			vec![names.get_main_name(PeerSet::Validation)],
+		);
+	}
+
+	#[test]
+	fn protocol_names_equality_depends_on_inputs() {
+		let genesis_hash = Hash::from([7; 32]);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("fork"));
+
+		assert_eq!(names, PeerSetProtocolNames::new(genesis_hash, Some("fork")));
+		assert_ne!(names, PeerSetProtocolNames::new(genesis_hash, Some("other-fork")));
+		assert_ne!(names, PeerSetProtocolNames::new(genesis_hash, None));
+	}
+
+	#[test]
+	fn default_set_config_per_role() {
+		use sc_network::config::NonReservedPeerMode;
+
+		let (validation_in, validation_out) = validation_slots();
+		for is_authority in [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority] {
+			let config = PeerSet::Validation.default_set_config(is_authority);
+			assert_eq!((config.in_peers, config.out_peers), (validation_in, validation_out));
+			assert!(config.reserved_nodes.is_empty());
+			assert!(matches!(config.non_reserved_mode, NonReservedPeerMode::Accept));
+
+			let config = PeerSet::Collation.default_set_config(is_authority);
+			assert_eq!(config.out_peers, 0);
+			assert!(config.reserved_nodes.is_empty());
+			if is_authority == IsAuthority::Yes {
+				assert_eq!(config.in_peers, PeerSetConfig::default().collation_authority_in_peers);
+				assert!(matches!(config.non_reserved_mode, NonReservedPeerMode::Accept));
+			} else {
+				assert_eq!(config.in_peers, 0);
+				assert!(matches!(config.non_reserved_mode, NonReservedPeerMode::Deny));
+			}
+		}
+	}
+
+	#[test]
+	fn per_peer_set_vec_indexes_like_per_peer_set() {
+		let named = PerPeerSet::new_with(|peer_set| peer_set.short_name());
+		let mut vec = PerPeerSetVec::from_fn(|peer_set| peer_set.short_name());
+		for peer_set in PeerSet::iter() {
+			assert_eq!(vec[peer_set], named[peer_set]);
+		}
+
+		vec[PeerSet::Collation] = "changed";
+		assert_eq!(vec[PeerSet::Validation], named[PeerSet::Validation]);
+		assert_eq!(vec[PeerSet::Collation], "changed");
+
+		let defaults = PerPeerSetVec::<u32>::default();
+		assert!(PeerSet::iter().all(|peer_set| defaults[peer_set] == 0));
+	}
+
+	#[test]
+	fn protocol_name_set_has_main_and_legacy_fallback() {
+		let protocol_names = PeerSetProtocolNames::new(Hash::from([9; 32]), None);
+		for peer_set in PeerSet::iter() {
+			let name_set = protocol_names.protocol_name_set(peer_set);
+			assert_eq!(name_set.main, protocol_names.get_main_name(peer_set));
+			assert!(name_set.fallbacks.contains(&peer_set.into_default_protocol_name()));
+			assert!(!name_set.fallbacks.contains(&name_set.main));
+		}
+	}
+
+	#[test]
+	fn metric_label_pairs_cover_all_peer_sets() {
+		let pairs = metric_label_pairs();
+		assert_eq!(pairs.len(), PeerSet::COUNT);
+		assert!(pairs.contains(&("validation", "validation/1")));
+		assert!(pairs.contains(&("collation", "collation/1")));
+	}
+
+	#[test]
+	fn rekey_genesis_rederives_genesis_names() {
+		let old_genesis = Hash::from([1; 32]);
+		let new_genesis = Hash::from([2; 32]);
+		let old_names = PeerSetProtocolNames::new(old_genesis, Some("fork"));
+		let rekeyed = old_names.clone().rekey_genesis(new_genesis).unwrap();
+
+		assert_eq!(rekeyed, PeerSetProtocolNames::new(new_genesis, Some("fork")));
+		for peer_set in PeerSet::iter() {
+			let main_name = rekeyed.get_main_name(peer_set);
+			assert!(main_name.starts_with(&format!("/{}/fork/", hex::encode(new_genesis))));
+			assert_eq!(rekeyed.try_get_protocol(&main_name), Some((peer_set, 1)));
+			assert_eq!(rekeyed.try_get_protocol(&old_names.get_main_name(peer_set)), None);
+			assert_eq!(
+				rekeyed.try_get_protocol(&peer_set.into_default_protocol_name()),
+				Some((peer_set, 1)),
+			);
+		}
+	}
+
+	#[test]
+	fn legacy_names_never_equal_generated_names() {
+		for genesis_hash in [Hash::from([1; 32]), Hash::repeat_byte(0xff), Hash::from([7; 32])] {
+			let names = PeerSetProtocolNames::new(genesis_hash, Some("fork"));
+			assert!(names.generated_names_are_not_legacy());
+			let generated = PeerSet::iter()
+				.flat_map(|peer_set| {
+					peer_set.default_version_range().map(move |version| (peer_set, version))
+				})
+				.map(|(peer_set, version)| names.get_name(peer_set, version))
+				.collect::<HashSet<_>>();
+			for peer_set in PeerSet::iter() {
+				for version in peer_set.default_version_range() {
+					let legacy_name = peer_set.into_protocol_name(version).unwrap();
+					assert!(!generated.contains(&legacy_name), "{} collides", legacy_name);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn peer_sets_by_notification_size_cover_all_peer_sets() {
+		for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+			let by_size = peer_sets_by_notification_size(is_authority);
+			assert_eq!(
+				by_size.iter().map(|(peer_set, _)| *peer_set).collect::<HashSet<_>>(),
+				PeerSet::iter().collect::<HashSet<_>>(),
+			);
+			assert!(by_size.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+			assert!(by_size
+				.iter()
+				.all(|(peer_set, size)| *size == peer_set.get_max_notification_size(is_authority)));
+		}
+	}
+
+	#[test]
+	fn gossip_out_capacity_is_validation_out_peers() {
+		let (_, out_peers) = validation_slots();
+		assert_eq!(gossip_out_capacity(IsAuthority::Yes), out_peers);
+		assert_eq!(gossip_out_capacity(IsAuthority::No), out_peers);
+		assert!((gossip_out_capacity(IsAuthority::Yes) as usize) < crate::MIN_GOSSIP_PEERS);
+	}
+
+	#[test]
+	fn version_status_of_current_deprecated_and_unknown_versions() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		for peer_set in PeerSet::iter() {
+			let current = peer_set.get_default_version();
+			assert_eq!(peer_set.version_status(current, &names), VersionStatus::Current);
+			assert_eq!(peer_set.version_status(current + 1, &names), VersionStatus::Unknown);
+			assert_eq!(peer_set.version_status(0, &names), VersionStatus::Unknown);
+		}
+
+		// Once a peer set moves on to v2, v1 is deprecated.
+		let builder = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x42))
+			.extra_version(PeerSet::Collation, 2)
+			.main_version(PeerSet::Collation, 2);
+		let names = builder.clone().build().unwrap();
+		assert_eq!(PeerSet::Collation.version_status(1, &names), VersionStatus::Deprecated);
+		assert_eq!(PeerSet::Collation.version_status(2, &names), VersionStatus::Current);
+		assert_eq!(PeerSet::Collation.version_status(3, &names), VersionStatus::Unknown);
+		assert_eq!(PeerSet::Validation.version_status(1, &names), VersionStatus::Current);
+
+		// Disabled fallbacks aren't accepted anymore.
+		let names = builder.disable_fallback(PeerSet::Collation, 1).build().unwrap();
+		assert_eq!(PeerSet::Collation.version_status(1, &names), VersionStatus::Unknown);
+		assert_eq!(PeerSet::Collation.version_status(2, &names), VersionStatus::Current);
+	}
+
+	#[test]
+	fn main_only_does_not_resolve_legacy_names() {
+		let names = PeerSetProtocolNames::main_only(Hash::repeat_byte(0x11), Some("fork"));
+		for peer_set in PeerSet::iter() {
+			let version = peer_set.get_default_version();
+			assert_eq!(
+				names.try_get_protocol(&names.get_main_name(peer_set)),
+				Some((peer_set, version))
+			);
+			assert_eq!(names.try_get_protocol(&peer_set.into_default_protocol_name()), None);
+		}
+	}
+
+	#[test]
+	fn every_peer_set_has_relevant_subsystems() {
+		for peer_set in PeerSet::iter() {
+			assert!(!peer_set.relevant_subsystems().is_empty(), "{:?}", peer_set);
+		}
+		assert!(PeerSet::Validation.relevant_subsystems().contains(&"statement-distribution"));
+		assert_eq!(PeerSet::Collation.relevant_subsystems(), &["collator-protocol"]);
+	}
+
+	#[test]
+	fn name_for_subsystem_resolves_its_peer_set() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x21), None);
+		assert_eq!(
+			names.name_for_subsystem("collator-protocol"),
+			Some(names.get_main_name(PeerSet::Collation)),
+		);
+		assert_eq!(
+			names.name_for_subsystem("statement-distribution"),
+			Some(names.get_main_name(PeerSet::Validation)),
+		);
+		assert_eq!(names.name_for_subsystem("unknown-subsystem"), None);
+	}
+
+	#[test]
+	fn peer_set_byte_roundtrip() {
+		assert_eq!(u8::from(PeerSet::Validation), 0);
+		assert_eq!(u8::from(PeerSet::Collation), 1);
+		for peer_set in PeerSet::iter() {
+			assert_eq!(PeerSet::try_from(u8::from(peer_set)), Ok(peer_set));
+		}
+		assert_eq!(PeerSet::try_from(2), Err(UnknownPeerSet(2)));
+		assert_eq!(PeerSet::try_from(u8::MAX), Err(UnknownPeerSet(u8::MAX)));
+	}
+
+	#[test]
+	fn peer_sets_info_registers_all_peer_sets() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x31), None);
+		for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+			assert_all_peer_sets_registered(&peer_sets_info(is_authority, &names), &names);
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "must be registered exactly once")]
+	fn truncated_peer_sets_info_misses_a_peer_set() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x31), None);
+		let mut configs = peer_sets_info(IsAuthority::Yes, &names);
+		configs.pop();
+		assert_all_peer_sets_registered(&configs, &names);
+	}
+
+	#[test]
+	fn reputation_decay_propagates_into_the_summary() {
+		assert!(
+			PeerSet::Validation.describe_config(IsAuthority::Yes).reputation_decay >
+				PeerSet::Collation.describe_config(IsAuthority::Yes).reputation_decay
+		);
+
+		let mut config = PeerSetConfig::default();
+		config.reputation_decay[PeerSet::Validation] = Duration::from_secs(120);
+		for peer_set in PeerSet::iter() {
+			for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+				let summary = peer_set.describe_config_with_config(is_authority, &config);
+				assert_eq!(summary.reputation_decay, config.reputation_decay[peer_set]);
+			}
+		}
+	}
+
+	#[test]
+	fn from_hex_genesis_parses_prefixed_and_bare_hashes() {
+		let genesis_hash = Hash::repeat_byte(0xab);
+		let expected = PeerSetProtocolNames::new(genesis_hash, Some("fork"));
+		let genesis_hex = hex::encode(genesis_hash);
+
+		assert_eq!(
+			PeerSetProtocolNames::from_hex_genesis(&format!("0x{}", genesis_hex), Some("fork")),
+			Ok(expected.clone()),
+		);
+		assert_eq!(
+			PeerSetProtocolNames::from_hex_genesis(&genesis_hex, Some("fork")),
+			Ok(expected)
+		);
+		assert_eq!(
+			PeerSetProtocolNames::from_hex_genesis("0xabab", None),
+			Err(HexDecodeError::InvalidHex(hex::FromHexError::InvalidStringLength)),
+		);
+		assert!(PeerSetProtocolNames::from_hex_genesis(&"zz".repeat(32), None).is_err());
+	}
+
+	#[test]
+	fn notifications_above_the_maximum_are_oversized() {
+		for peer_set in PeerSet::iter() {
+			for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+				let max = peer_set.get_max_notification_size(is_authority) as usize;
+				assert!(!peer_set.is_notification_oversized(is_authority, max - 1));
+				assert!(!peer_set.is_notification_oversized(is_authority, max));
+				assert!(peer_set.is_notification_oversized(is_authority, max + 1));
+			}
+		}
+	}
+
+	#[test]
+	fn validation_has_priority_over_collation() {
+		for is_authority in [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority] {
+			assert_eq!(
+				peer_sets_ordered_by_priority(is_authority),
+				vec![PeerSet::Validation, PeerSet::Collation],
+			);
+		}
+	}
+
+	#[test]
+	fn prefix_with_and_without_fork() {
+		let genesis_hash = Hash::from([1; 32]);
+		let genesis_hex = hex::encode(genesis_hash);
+
+		let names = PeerSetProtocolNames::new(genesis_hash, None);
+		assert_eq!(names.prefix(), format!("/{}", genesis_hex));
+		assert_eq!(names.prefix(), PeerSetProtocolNames::name_prefix(&genesis_hash, None));
+
+		let names = PeerSetProtocolNames::new(genesis_hash, Some("test-fork"));
+		assert_eq!(names.prefix(), format!("/{}/test-fork", genesis_hex));
+		assert_eq!(
+			names.prefix(),
+			PeerSetProtocolNames::name_prefix(&genesis_hash, Some("test-fork")),
+		);
+		for peer_set in PeerSet::iter() {
+			assert!(names.get_main_name(peer_set).starts_with(names.prefix()));
+		}
+		assert!(!PeerSetProtocolNames::new(Hash::from([2; 32]), Some("test-fork"))
+			.get_main_name(PeerSet::Validation)
+			.starts_with(names.prefix()));
+	}
+
+	#[test]
+	fn main_version_can_be_overridden() {
+		let genesis_hash = Hash::repeat_byte(0x46);
+		let names = PeerSetProtocolNames::builder()
+			.genesis(genesis_hash)
+			.extra_version(PeerSet::Collation, 2)
+			.main_version(PeerSet::Collation, 2)
+			.build()
+			.unwrap();
+
+		assert_eq!(names.get_main_version(PeerSet::Validation), 1);
+		assert_eq!(names.get_main_version(PeerSet::Collation), 2);
+		assert_eq!(
+			names.get_main_name(PeerSet::Validation),
+			PeerSetProtocolNames::generate_name(&genesis_hash, None, PeerSet::Validation, 1),
+		);
+		assert_eq!(
+			names.get_main_name(PeerSet::Collation),
+			PeerSetProtocolNames::generate_name(&genesis_hash, None, PeerSet::Collation, 2),
+		);
+		assert_eq!(
+			names.fallback_names(PeerSet::Collation),
+			vec![
+				names.get_name(PeerSet::Collation, 1),
+				PeerSet::Collation.into_default_protocol_name()
+			],
+		);
+		assert_eq!(
+			names.fallback_names(PeerSet::Validation),
+			PeerSetProtocolNames::get_fallback_names(PeerSet::Validation),
+		);
+
+		assert_eq!(
+			PeerSetProtocolNames::builder()
+				.genesis(genesis_hash)
+				.main_version(PeerSet::Collation, 2)
+				.build(),
+			Err(ProtocolNameError::MissingMainVersion(PeerSet::Collation, 2)),
+		);
+	}
+
+	#[test]
+	fn all_peer_sets_match_iter() {
+		assert_eq!(PeerSet::ALL.len(), PeerSet::COUNT);
+		assert_eq!(PeerSet::ALL.to_vec(), PeerSet::iter().collect::<Vec<_>>());
+		for (index, peer_set) in PeerSet::ALL.iter().enumerate() {
+			assert_eq!(peer_set.index(), index);
+		}
+	}
+
+	#[test]
+	fn disabled_fallbacks_resolve_but_are_not_accepted() {
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x89))
+			.extra_version(PeerSet::Validation, 2)
+			.main_version(PeerSet::Validation, 2)
+			.disable_fallback(PeerSet::Validation, 1)
+			.build()
+			.unwrap();
+
+		let disabled = [
+			names.get_name(PeerSet::Validation, 1),
+			PeerSet::Validation.into_default_protocol_name(),
+		];
+		for name in &disabled {
+			assert_eq!(names.try_get_protocol(name), Some((PeerSet::Validation, 1)));
+			assert!(!names.is_accepted(name));
+		}
+		assert!(names.fallback_names(PeerSet::Validation).is_empty());
+		assert!(names.is_accepted(&names.get_main_name(PeerSet::Validation)));
+
+		let collation_legacy = PeerSet::Collation.into_default_protocol_name();
+		assert!(names.is_accepted(&names.get_main_name(PeerSet::Collation)));
+		assert!(names.is_accepted(&collation_legacy));
+		assert_eq!(names.fallback_names(PeerSet::Collation), vec![collation_legacy]);
+		assert!(!names.is_accepted("/unknown/1"));
+
+		assert_eq!(
+			PeerSetProtocolNames::builder()
+				.genesis(Hash::repeat_byte(0x42))
+				.disable_fallback(PeerSet::Collation, 1)
+				.build(),
+			Err(ProtocolNameError::DisabledMainVersion(PeerSet::Collation, 1)),
+		);
+	}
+
+	#[test]
+	fn set_configs_equivalent_compares_all_fields() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		for peer_set in PeerSet::iter() {
+			let info = peer_set.get_info(IsAuthority::Yes, &names);
+			assert!(set_configs_equivalent(&info, &peer_set.get_info(IsAuthority::Yes, &names)));
+
+			let mut mutated = peer_set.get_info(IsAuthority::Yes, &names);
+			mutated.set_config.in_peers += 1;
+			assert!(!set_configs_equivalent(&info, &mutated));
+
+			let mut mutated = peer_set.get_info(IsAuthority::Yes, &names);
+			mutated.fallback_names.push("/polkadot/unknown/1".into());
+			assert!(!set_configs_equivalent(&info, &mutated));
+		}
+		assert!(!set_configs_equivalent(
+			&PeerSet::Collation.get_info(IsAuthority::Yes, &names),
+			&PeerSet::Collation.get_info(IsAuthority::No, &names),
+		));
+	}
+
+	#[test]
+	fn max_notification_size_per_role() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		assert_eq!(
+			PeerSet::Collation.get_max_notification_size(IsAuthority::Yes),
+			MAX_COLLATION_AUTHORITY_NOTIFICATION_SIZE,
+		);
+		for is_authority in [IsAuthority::No, IsAuthority::AboutToBeAuthority] {
+			assert_eq!(
+				PeerSet::Collation.get_max_notification_size(is_authority),
+				DEFAULT_MAX_NOTIFICATION_SIZE,
+			);
+		}
+
+		for is_authority in [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority] {
+			assert_eq!(
+				PeerSet::Validation.get_max_notification_size(is_authority),
+				DEFAULT_MAX_NOTIFICATION_SIZE,
+			);
+			for peer_set in PeerSet::iter() {
+				assert_eq!(
+					peer_set.get_info(is_authority, &names).max_notification_size,
+					peer_set.get_max_notification_size(is_authority),
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn produced_set_configs_are_valid() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		for is_authority in [IsAuthority::Yes, IsAuthority::No, IsAuthority::AboutToBeAuthority] {
+			for info in peer_sets_info(is_authority, &names) {
+				assert_eq!(validate_set_config(&info, &names), Ok(()));
+			}
+		}
+	}
+
+	#[test]
+	fn broken_set_configs_are_rejected() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let mut info = PeerSet::Collation.get_info(IsAuthority::Yes, &names);
+		info.set_config.out_peers = 1;
+		assert_eq!(validate_set_config(&info, &names), Err(SetConfigError::CollationOutPeers(1)));
+
+		let mut info = PeerSet::Collation.get_info(IsAuthority::No, &names);
+		info.set_config.in_peers = 5;
+		assert!(matches!(
+			validate_set_config(&info, &names),
+			Err(SetConfigError::NonReservedModeMismatch { in_peers: 5, out_peers: 0, .. })
+		));
+
+		let mut info = PeerSet::Validation.get_info(IsAuthority::Yes, &names);
+		info.max_notification_size = MAX_NOTIFICATION_SIZE_CEILING + 1;
+		assert_eq!(
+			validate_set_config(&info, &names),
+			Err(SetConfigError::NotificationSizeTooLarge(MAX_NOTIFICATION_SIZE_CEILING + 1)),
+		);
+
+		let mut info = PeerSet::Validation.get_info(IsAuthority::Yes, &names);
+		info.notifications_protocol = "/unknown/1".into();
+		assert_eq!(
+			validate_set_config(&info, &names),
+			Err(SetConfigError::UnknownProtocol("/unknown/1".into())),
+		);
+	}
+
+	#[test]
+	fn accepting_without_slots_is_valid() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x42), None);
+		let mut info = PeerSet::Validation.get_info(IsAuthority::Yes, &names);
+		info.set_config.in_peers = 0;
+		info.set_config.out_peers = 0;
+		assert_eq!(
+			info.set_config.non_reserved_mode,
+			sc_network::config::NonReservedPeerMode::Accept
+		);
+		assert_eq!(validate_set_config(&info, &names), Ok(()));
+	}
+
+	#[test]
+	fn supported_versions_have_a_release_tag() {
+		for peer_set in PeerSet::iter() {
+			for version in peer_set.default_version_range() {
+				let tag = peer_set.version_introduced_in(version);
+				assert!(matches!(tag, Some(tag) if !tag.is_empty()), "{:?} {}", peer_set, version);
+			}
+			assert_eq!(peer_set.version_introduced_in(peer_set.get_default_version() + 1), None);
+		}
+	}
+
+	#[test]
+	fn empty_fork_id_is_no_fork_id() {
+		let genesis_hash = Hash::repeat_byte(0x94);
+		let names = PeerSetProtocolNames::new(genesis_hash, Some(""));
+
+		assert_eq!(names, PeerSetProtocolNames::new(genesis_hash, None));
+		for peer_set in PeerSet::iter() {
+			let main_name = names.get_main_name(peer_set);
+			assert!(!main_name.contains("//"), "{}", main_name);
+			assert_eq!(
+				PeerSetProtocolNames::generate_name(&genesis_hash, Some(""), peer_set, 1),
+				PeerSetProtocolNames::generate_name(&genesis_hash, None, peer_set, 1),
+			);
+		}
+	}
+
+	#[test]
+	fn only_collation_requires_authority() {
+		assert!(PeerSet::Collation.requires_authority());
+		assert!(!PeerSet::Validation.requires_authority());
+	}
+
+	#[test]
+	fn configured_handshakes_propagate_into_the_summary() {
+		for peer_set in PeerSet::iter() {
+			assert_eq!(peer_set.describe_config(IsAuthority::Yes).handshake, None);
+		}
+
+		let mut config = PeerSetConfig::default();
+		config.handshakes[PeerSet::Collation] = Some(vec![1, 0b101]);
+		assert_eq!(
+			PeerSet::Collation
+				.describe_config_with_config(IsAuthority::Yes, &config)
+				.handshake,
+			Some(vec![1, 0b101]),
+		);
+		assert_eq!(
+			PeerSet::Validation
+				.describe_config_with_config(IsAuthority::Yes, &config)
+				.handshake,
+			None,
+		);
+	}
+
+	#[test]
+	fn peer_sets_summary_covers_all_peer_sets() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x97), None);
+		for is_authority in [IsAuthority::Yes, IsAuthority::No] {
+			let summary = peer_sets_summary(is_authority, &names);
+			assert_eq!(summary.is_authority, is_authority);
+			for peer_set in PeerSet::iter() {
+				assert_eq!(summary.configs[peer_set], peer_set.describe_config(is_authority));
+				assert_eq!(summary.names[peer_set].main, names.get_main_name(peer_set));
+				assert_eq!(summary.names[peer_set].fallbacks, names.fallback_names(peer_set));
+			}
+		}
+	}
+
+	#[test]
+	fn zero_genesis_hash_is_rejected() {
+		let versions = PerPeerSet::new_with(|peer_set| vec![peer_set.get_default_version()]);
+		assert_eq!(
+			PeerSetProtocolNames::new_with_versions(Hash::zero(), None, &versions),
+			Err(ProtocolNameError::ZeroGenesisHash),
+		);
+		assert_eq!(
+			PeerSetProtocolNames::new_with_versions(Hash::repeat_byte(0x98), None, &versions),
+			Ok(PeerSetProtocolNames::new(Hash::repeat_byte(0x98), None)),
+		);
+		assert_eq!(
+			PeerSetProtocolNames::builder().build().unwrap_err(),
+			ProtocolNameError::ZeroGenesisHash,
+		);
+		assert_eq!(
+			PeerSetProtocolNames::builder()
+				.genesis(Hash::zero())
+				.prefix("/custom".to_owned())
+				.build()
+				.unwrap_err(),
+			ProtocolNameError::ZeroGenesisHash,
+		);
+		assert_eq!(
+			PeerSetProtocolNames::try_new(Hash::zero(), None),
+			Err(ProtocolNameError::ZeroGenesisHash),
+		);
+		assert_eq!(
+			PeerSetProtocolNames::try_new(Hash::repeat_byte(0x98), None),
+			Ok(PeerSetProtocolNames::new(Hash::repeat_byte(0x98), None)),
+		);
+		assert!(std::panic::catch_unwind(|| PeerSetProtocolNames::new(Hash::zero(), None)).is_err());
+		assert_eq!(
+			PeerSetProtocolNames::from_hex_genesis(&format!("0x{}", "00".repeat(32)), None),
+			Err(HexDecodeError::ZeroGenesisHash),
+		);
+	}
+
+	#[test]
+	fn all_names_start_with_the_main_name() {
+		let names = PeerSetProtocolNames::new(Hash::repeat_byte(0x99), None);
+		for peer_set in PeerSet::iter() {
+			assert_eq!(
+				names.all_names(peer_set),
+				vec![names.get_main_name(peer_set), peer_set.into_default_protocol_name()],
+			);
+		}
+
+		// Legacy names can serve as main names, but are listed only once.
+		let names = PeerSetProtocolNames::builder()
+			.genesis(Hash::repeat_byte(0x99))
+			.prefix("/polkadot".into())
+			.build()
+			.unwrap();
+		for peer_set in PeerSet::iter() {
+			assert_eq!(names.all_names(peer_set), vec![peer_set.into_default_protocol_name()]);
+		}
+	}
 }
@@ -252,6 +252,18 @@ impl Protocol {
 		std::iter::once(self.get_legacy_name().into()).collect()
 	}
 
+	/// Protocol name of this protocol, without the genesis hash & fork id prefix.
+	pub(crate) const fn get_short_name(self) -> &'static str {
+		match self {
+			Protocol::ChunkFetchingV1 => "/req_chunk/1",
+			Protocol::CollationFetchingV1 => "/req_collation/1",
+			Protocol::PoVFetchingV1 => "/req_pov/1",
+			Protocol::AvailableDataFetchingV1 => "/req_available_data/1",
+			Protocol::StatementFetchingV1 => "/req_statement/1",
+			Protocol::DisputeSendingV1 => "/send_dispute/1",
+		}
+	}
+
 	/// Legacy protocol name associated with each peer set.
 	const fn get_legacy_name(self) -> &'static str {
 		match self {
@@ -309,15 +321,6 @@ impl ReqProtocolNames {
 			format!("/{}", hex::encode(genesis_hash))
 		};
 
-		let short_name = match protocol {
-			Protocol::ChunkFetchingV1 => "/req_chunk/1",
-			Protocol::CollationFetchingV1 => "/req_collation/1",
-			Protocol::PoVFetchingV1 => "/req_pov/1",
-			Protocol::AvailableDataFetchingV1 => "/req_available_data/1",
-			Protocol::StatementFetchingV1 => "/req_statement/1",
-			Protocol::DisputeSendingV1 => "/send_dispute/1",
-		};
-
-		format!("{}{}", prefix, short_name).into()
+		format!("{}{}", prefix, protocol.get_short_name()).into()
 	}
 }
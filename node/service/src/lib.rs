@@ -46,7 +46,10 @@ use {
 		self as chain_selection_subsystem, Config as ChainSelectionConfig,
 	},
 	polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig,
-	polkadot_node_network_protocol::request_response::ReqProtocolNames,
+	polkadot_node_network_protocol::{
+		peer_set::{PeerSetProtocolNames, ProtocolNameError},
+		request_response::ReqProtocolNames,
+	},
 	polkadot_overseer::BlockInfo,
 	sc_client_api::{BlockBackend, ExecutorProvider},
 	sp_core::traits::SpawnNamed,
@@ -225,6 +228,10 @@ pub enum Error {
 	#[error(transparent)]
 	Jaeger(#[from] polkadot_node_subsystem::jaeger::JaegerError),
 
+	#[cfg(feature = "full-node")]
+	#[error("Invalid peer set protocol names: {0}")]
+	PeerSetProtocolNames(#[from] ProtocolNameError),
+
 	#[cfg(feature = "full-node")]
 	#[error(transparent)]
 	Availability(#[from] AvailabilityError),
@@ -852,10 +859,16 @@ where
 			.push(beefy_gadget::beefy_peers_set_config(beefy_protocol_name.clone()));
 	}
 
+	let peerset_protocol_names =
+		PeerSetProtocolNames::try_new(genesis_hash, config.chain_spec.fork_id())?;
+
 	{
 		use polkadot_network_bridge::{peer_sets_info, IsAuthority};
 		let is_authority = if role.is_authority() { IsAuthority::Yes } else { IsAuthority::No };
-		config.network.extra_sets.extend(peer_sets_info(is_authority));
+		config
+			.network
+			.extra_sets
+			.extend(peer_sets_info(is_authority, &peerset_protocol_names));
 	}
 
 	let req_protocol_names = ReqProtocolNames::new(&genesis_hash, config.chain_spec.fork_id());
@@ -1063,6 +1076,7 @@ where
 					pvf_checker_enabled,
 					overseer_message_channel_capacity_override,
 					req_protocol_names,
+					peerset_protocol_names,
 				},
 			)
 			.map_err(|e| {
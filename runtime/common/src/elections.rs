@@ -16,6 +16,13 @@
 
 //! Code for elections.
 
+use frame_support::{
+	traits::Get,
+	weights::{RuntimeDbWeight, Weight},
+};
+use pallet_election_provider_multi_phase::{BenchmarkingConfig, WeightInfo};
+use sp_std::marker::PhantomData;
+
 /// Implements the weight types for the elections module and a specific
 /// runtime.
 /// This macro should not be called directly; use [`impl_runtime_weights`] instead.
@@ -60,3 +67,580 @@ impl pallet_election_provider_multi_phase::BenchmarkingConfig for BenchmarkConfi
 
 /// The accuracy type used for genesis election provider;
 pub type OnChainAccuracy = sp_runtime::Perbill;
+
+/// Base weight and per component slopes of a benchmarked election weight function, excluding
+/// its storage accesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElectionWeightCoeffs {
+	/// Weight independent of the components.
+	pub base: Weight,
+	/// Weight per voter.
+	pub per_v: Weight,
+	/// Weight per target.
+	pub per_t: Weight,
+	/// Weight per active voter.
+	pub per_a: Weight,
+	/// Weight per desired target.
+	pub per_d: Weight,
+}
+
+/// Placeholder weight of a single comparison in [`ElectionWeights::sort_winners`], pending a
+/// benchmark of the winner sort.
+pub const PLACEHOLDER_SORT_COMPARISON_WEIGHT: Weight = 25_000;
+
+/// Placeholder weight, besides its single write, of [`ElectionWeights::store_solution_hash`] and
+/// [`ElectionWeights::cleanup_replaced_solution`]. The pallet does not keep solution hashes yet, so
+/// there is nothing to benchmark.
+pub const PLACEHOLDER_SOLUTION_HASH_WEIGHT: Weight = 2_000_000;
+
+/// Weight functions for `pallet_election_provider_multi_phase` paths and queries not covered by
+/// its `WeightInfo` trait.
+///
+/// They are derived from the benchmarked weights `W` of a runtime, which are evaluated on the
+/// components [`BenchmarkConfig`] was benchmarked over, and from its database weights `D`.
+pub struct ElectionWeights<D, W>(PhantomData<(D, W)>);
+
+impl<D: Get<RuntimeDbWeight>, W: WeightInfo> ElectionWeights<D, W> {
+	/// The `(reads, writes)` storage accesses of the `WeightInfo` function `name`, if any.
+	///
+	/// These follow the storage annotations of the benchmarks, which the generated weights don't
+	/// otherwise expose.
+	pub fn storage_accesses(name: &str) -> Option<(u32, u32)> {
+		match name {
+			"on_initialize_nothing" => Some((8, 0)),
+			"on_initialize_open_signed" => Some((1, 1)),
+			"on_initialize_open_unsigned" => Some((1, 1)),
+			"finalize_signed_phase_accept_solution" => Some((1, 2)),
+			"finalize_signed_phase_reject_solution" => Some((1, 1)),
+			"create_snapshot_internal" => Some((0, 3)),
+			"elect_queued" => Some((7, 9)),
+			"submit" => Some((5, 3)),
+			"submit_unsigned" => Some((7, 1)),
+			"feasibility_check" => Some((4, 0)),
+			_ => None,
+		}
+	}
+
+	/// The coefficients of `submit_unsigned`.
+	pub fn submit_unsigned_coeffs() -> ElectionWeightCoeffs {
+		Self::coeffs(W::submit_unsigned, "submit_unsigned")
+	}
+
+	/// The coefficients of `feasibility_check`.
+	pub fn feasibility_check_coeffs() -> ElectionWeightCoeffs {
+		Self::coeffs(W::feasibility_check, "feasibility_check")
+	}
+
+	/// The coefficients of `elect_queued`, which only depends on `a` and `d`.
+	pub fn elect_queued_coeffs() -> ElectionWeightCoeffs {
+		Self::coeffs(|_, _, a, d| W::elect_queued(a, d), "elect_queued")
+	}
+
+	/// The coefficients of `create_snapshot_internal`, which only depends on `v` and `t`.
+	pub fn create_snapshot_internal_coeffs() -> ElectionWeightCoeffs {
+		Self::coeffs(|v, t, _, _| W::create_snapshot_internal(v, t), "create_snapshot_internal")
+	}
+
+	/// Whether all components lie within their benchmarked ranges, bounds included. Weights of
+	/// components outside of them are extrapolated.
+	pub fn within_benchmarked_range(v: u32, t: u32, a: u32, d: u32) -> bool {
+		let within = |n: u32, [low, high]: [u32; 2]| low <= n && n <= high;
+		within(v, BenchmarkConfig::VOTERS) &&
+			within(t, BenchmarkConfig::TARGETS) &&
+			within(a, BenchmarkConfig::ACTIVE_VOTERS) &&
+			within(d, BenchmarkConfig::DESIRED_TARGETS)
+	}
+
+	/// `submit_unsigned` with each component clamped into its benchmarked range, additionally
+	/// reporting whether any component had to be clamped.
+	pub fn submit_unsigned_clamped(v: u32, t: u32, a: u32, d: u32) -> (Weight, bool) {
+		let clamp = |n: u32, [low, high]: [u32; 2]| n.max(low).min(high);
+		let clamped = (
+			clamp(v, BenchmarkConfig::VOTERS),
+			clamp(t, BenchmarkConfig::TARGETS),
+			clamp(a, BenchmarkConfig::ACTIVE_VOTERS),
+			clamp(d, BenchmarkConfig::DESIRED_TARGETS),
+		);
+		let weight = W::submit_unsigned(clamped.0, clamped.1, clamped.2, clamped.3);
+		(weight, clamped != (v, t, a, d))
+	}
+
+	/// A `feasibility_check` bailing out on the first invalid edge, after checking
+	/// `edges_checked` edges. Each edge is priced like an active voter's assignment.
+	pub fn feasibility_check_early_exit(edges_checked: u32) -> Weight {
+		W::feasibility_check(0, 0, 0, 0).saturating_add(
+			Self::feasibility_check_coeffs().per_a.saturating_mul(edges_checked as Weight),
+		)
+	}
+
+	/// Sorting the `w` elected winners before storing them, priced as `w * log2(w)`
+	/// comparisons.
+	///
+	/// This is a placeholder, see [`PLACEHOLDER_SORT_COMPARISON_WEIGHT`].
+	pub fn sort_winners(w: u32) -> Weight {
+		// Rounded up, and at least one comparison per winner.
+		let log_w = (u32::BITS - w.saturating_sub(1).leading_zeros()).max(1);
+		PLACEHOLDER_SORT_COMPARISON_WEIGHT
+			.saturating_mul(w as Weight)
+			.saturating_mul(log_w as Weight)
+	}
+
+	/// Re-predicting the next election and re-deriving the current phase after a phase length
+	/// change. Priced like `on_initialize_nothing`, plus the phase update.
+	pub fn recompute_phase_length() -> Weight {
+		W::on_initialize_nothing().saturating_add(D::get().writes(1))
+	}
+
+	/// Storing the hash of an accepted solution, to later reject duplicates.
+	///
+	/// This is a placeholder, see [`PLACEHOLDER_SOLUTION_HASH_WEIGHT`].
+	pub fn store_solution_hash() -> Weight {
+		PLACEHOLDER_SOLUTION_HASH_WEIGHT.saturating_add(D::get().writes(1))
+	}
+
+	/// `create_snapshot_internal` when both voters and targets hit their bounds early, priced
+	/// over the `v_taken` voters and `t_taken` targets only.
+	pub fn create_snapshot_double_bounded(v_taken: u32, t_taken: u32) -> Weight {
+		W::create_snapshot_internal(v_taken, t_taken)
+	}
+
+	/// `submit_unsigned`, additionally reporting whether composing it from its coefficients
+	/// saturated.
+	#[cfg(test)]
+	pub fn submit_unsigned_checked(v: u32, t: u32, a: u32, d: u32) -> (Weight, bool) {
+		let coeffs = Self::submit_unsigned_coeffs();
+		let checked = [(coeffs.per_v, v), (coeffs.per_t, t), (coeffs.per_a, a), (coeffs.per_d, d)]
+			.iter()
+			.try_fold(coeffs.base, |acc, (slope, n)| {
+				acc.checked_add(slope.checked_mul(*n as Weight)?)
+			})
+			.and_then(|weight| {
+				weight.checked_add(Self::checked_storage_weight("submit_unsigned")?)
+			});
+		let weight = W::submit_unsigned(v, t, a, d);
+		if let Some(checked) = checked {
+			debug_assert_eq!(checked, weight);
+		}
+		(weight, checked.is_none())
+	}
+
+	/// Entering the emergency phase when no valid solution is available. Priced like opening
+	/// a phase, without reading the round.
+	pub fn on_initialize_enter_emergency() -> Weight {
+		W::on_initialize_open_unsigned().saturating_sub(D::get().reads(1))
+	}
+
+	/// Checking that each of the `d` edges of a solution points to a target its voter actually
+	/// nominated, on top of the in-bounds index checks. Priced like `feasibility_check`'s
+	/// per-`d` slope, the snapshot being already in memory.
+	pub fn validate_edge_nominations(d: u32) -> Weight {
+		Self::feasibility_check_coeffs().per_d.saturating_mul(d as Weight)
+	}
+
+	/// Clearing the auxiliary entries of a queued solution superseded by a better one, i.e. killing
+	/// the hash stored by `store_solution_hash`.
+	///
+	/// This is a placeholder, see [`PLACEHOLDER_SOLUTION_HASH_WEIGHT`].
+	pub fn cleanup_replaced_solution() -> Weight {
+		PLACEHOLDER_SOLUTION_HASH_WEIGHT.saturating_add(D::get().writes(1))
+	}
+
+	/// The fixed weight of `elect_queued`, i.e. its weight without any active voters or desired
+	/// targets. Unlike [`ElectionWeightCoeffs::base`], this includes its DB reads and writes.
+	pub fn elect_queued_fixed_weight() -> Weight {
+		W::elect_queued(0, 0)
+	}
+
+	/// `elect_queued`, or `None` if composing it from its coefficients overflows.
+	pub fn elect_queued_checked(a: u32, d: u32) -> Option<Weight> {
+		let coeffs = Self::elect_queued_coeffs();
+		coeffs
+			.base
+			.checked_add(coeffs.per_a.checked_mul(a as Weight)?)?
+			.checked_add(coeffs.per_d.checked_mul(d as Weight)?)?
+			.checked_add(Self::checked_storage_weight("elect_queued")?)
+	}
+
+	/// The weight `submit_unsigned` adds per voter `v`.
+	pub fn submit_unsigned_marginal_weight_per_voter() -> Weight {
+		Self::submit_unsigned_coeffs().per_v
+	}
+
+	/// The weight `submit_unsigned` adds per target `t`.
+	pub fn submit_unsigned_marginal_weight_per_target() -> Weight {
+		Self::submit_unsigned_coeffs().per_t
+	}
+
+	/// The weight `submit_unsigned` adds per active voter `a`, usually its steepest component.
+	pub fn submit_unsigned_marginal_weight_per_active_voter() -> Weight {
+		Self::submit_unsigned_coeffs().per_a
+	}
+
+	/// The weight `submit_unsigned` adds per desired target `d`.
+	pub fn submit_unsigned_marginal_weight_per_desired_target() -> Weight {
+		Self::submit_unsigned_coeffs().per_d
+	}
+
+	/// Recovers the coefficients of the linear weight function `f` by evaluating it around zero
+	/// components.
+	fn coeffs(f: impl Fn(u32, u32, u32, u32) -> Weight, name: &str) -> ElectionWeightCoeffs {
+		let zero = f(0, 0, 0, 0);
+		ElectionWeightCoeffs {
+			base: zero.saturating_sub(Self::storage_weight(name)),
+			per_v: f(1, 0, 0, 0).saturating_sub(zero),
+			per_t: f(0, 1, 0, 0).saturating_sub(zero),
+			per_a: f(0, 0, 1, 0).saturating_sub(zero),
+			per_d: f(0, 0, 0, 1).saturating_sub(zero),
+		}
+	}
+
+	/// The weight of the storage accesses of `name`.
+	fn storage_weight(name: &str) -> Weight {
+		Self::storage_accesses(name)
+			.map_or(0, |(reads, writes)| D::get().reads_writes(reads as Weight, writes as Weight))
+	}
+
+	/// The weight of the storage accesses of `name`, or `None` if unknown or overflowing.
+	fn checked_storage_weight(name: &str) -> Option<Weight> {
+		let (reads, writes) = Self::storage_accesses(name)?;
+		let db = D::get();
+		db.read
+			.checked_mul(reads as Weight)?
+			.checked_add(db.write.checked_mul(writes as Weight)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::weights::constants::RocksDbWeight;
+
+	/// Linear weights with every slope of a function distinct, so that mixed up components
+	/// show.
+	struct MockWeights;
+
+	impl WeightInfo for MockWeights {
+		fn on_initialize_nothing() -> Weight {
+			13_000_000 + RocksDbWeight::get().reads(8)
+		}
+		fn on_initialize_open_signed() -> Weight {
+			12_000_000 + RocksDbWeight::get().reads_writes(1, 1)
+		}
+		fn on_initialize_open_unsigned() -> Weight {
+			12_000_000 + RocksDbWeight::get().reads_writes(1, 1)
+		}
+		fn finalize_signed_phase_accept_solution() -> Weight {
+			26_000_000 + RocksDbWeight::get().reads_writes(1, 2)
+		}
+		fn finalize_signed_phase_reject_solution() -> Weight {
+			19_000_000 + RocksDbWeight::get().reads_writes(1, 1)
+		}
+		fn create_snapshot_internal(v: u32, t: u32) -> Weight {
+			400_000 * v as Weight + 90_000 * t as Weight + RocksDbWeight::get().writes(3)
+		}
+		fn elect_queued(a: u32, d: u32) -> Weight {
+			15_000_000 +
+				900_000 * a as Weight +
+				160_000 * d as Weight +
+				RocksDbWeight::get().reads_writes(7, 9)
+		}
+		fn submit() -> Weight {
+			47_000_000 + RocksDbWeight::get().reads_writes(5, 3)
+		}
+		fn submit_unsigned(v: u32, t: u32, a: u32, d: u32) -> Weight {
+			1_200_000 * v as Weight +
+				110_000 * t as Weight +
+				8_200_000 * a as Weight +
+				1_600_000 * d as Weight +
+				RocksDbWeight::get().reads_writes(7, 1)
+		}
+		fn feasibility_check(v: u32, t: u32, a: u32, d: u32) -> Weight {
+			1_100_000 * v as Weight +
+				70_000 * t as Weight +
+				6_000_000 * a as Weight +
+				1_300_000 * d as Weight +
+				RocksDbWeight::get().reads(4)
+		}
+	}
+
+	/// [`MockWeights`] mis-scaled by a million, so that large components saturate.
+	struct MisScaledWeights;
+
+	const MIS_SCALE: Weight = 1_000_000;
+
+	impl WeightInfo for MisScaledWeights {
+		fn on_initialize_nothing() -> Weight {
+			MockWeights::on_initialize_nothing().saturating_mul(MIS_SCALE)
+		}
+		fn on_initialize_open_signed() -> Weight {
+			MockWeights::on_initialize_open_signed().saturating_mul(MIS_SCALE)
+		}
+		fn on_initialize_open_unsigned() -> Weight {
+			MockWeights::on_initialize_open_unsigned().saturating_mul(MIS_SCALE)
+		}
+		fn finalize_signed_phase_accept_solution() -> Weight {
+			MockWeights::finalize_signed_phase_accept_solution().saturating_mul(MIS_SCALE)
+		}
+		fn finalize_signed_phase_reject_solution() -> Weight {
+			MockWeights::finalize_signed_phase_reject_solution().saturating_mul(MIS_SCALE)
+		}
+		fn create_snapshot_internal(v: u32, t: u32) -> Weight {
+			MockWeights::create_snapshot_internal(v, t).saturating_mul(MIS_SCALE)
+		}
+		fn elect_queued(a: u32, d: u32) -> Weight {
+			MockWeights::elect_queued(a, d).saturating_mul(MIS_SCALE)
+		}
+		fn submit() -> Weight {
+			MockWeights::submit().saturating_mul(MIS_SCALE)
+		}
+		fn submit_unsigned(v: u32, t: u32, a: u32, d: u32) -> Weight {
+			MockWeights::submit_unsigned(v, t, a, d).saturating_mul(MIS_SCALE)
+		}
+		fn feasibility_check(v: u32, t: u32, a: u32, d: u32) -> Weight {
+			MockWeights::feasibility_check(v, t, a, d).saturating_mul(MIS_SCALE)
+		}
+	}
+
+	type Weights = ElectionWeights<RocksDbWeight, MockWeights>;
+
+	#[test]
+	fn feasibility_check_early_exit_is_cheaper_than_full_check() {
+		let full = MockWeights::feasibility_check(1000, 500, 500, 200);
+
+		assert!(Weights::feasibility_check_early_exit(1) < full);
+		assert!(Weights::feasibility_check_early_exit(499) < full);
+		assert!(
+			Weights::feasibility_check_early_exit(1) < Weights::feasibility_check_early_exit(499)
+		);
+	}
+
+	#[test]
+	fn sort_winners_grows_superlinearly() {
+		assert!(Weights::sort_winners(1) > 0);
+		assert!(Weights::sort_winners(200) > 2 * Weights::sort_winners(100));
+		assert!(Weights::sort_winners(1000) > 10 * Weights::sort_winners(100));
+	}
+
+	#[test]
+	fn recompute_phase_length_storage_accesses() {
+		let db = RocksDbWeight::get();
+
+		assert_eq!(
+			Weights::recompute_phase_length(),
+			MockWeights::on_initialize_nothing() + db.writes(1),
+		);
+		assert_eq!(Weights::recompute_phase_length(), 13_000_000 + db.reads(8) + db.writes(1));
+	}
+
+	#[test]
+	fn store_solution_hash_is_one_write() {
+		assert_eq!(
+			Weights::store_solution_hash(),
+			PLACEHOLDER_SOLUTION_HASH_WEIGHT + RocksDbWeight::get().writes(1)
+		);
+	}
+
+	#[test]
+	fn double_bounded_snapshot_is_cheaper_than_full_range() {
+		let full = MockWeights::create_snapshot_internal(2000, 1000);
+
+		assert!(Weights::create_snapshot_double_bounded(1500, 700) < full);
+		assert_eq!(Weights::create_snapshot_double_bounded(2000, 1000), full);
+	}
+
+	#[test]
+	fn submit_unsigned_matches_its_coeffs() {
+		let db = RocksDbWeight::get();
+		let coeffs = Weights::submit_unsigned_coeffs();
+
+		assert_eq!(
+			coeffs,
+			ElectionWeightCoeffs {
+				base: 0,
+				per_v: 1_200_000,
+				per_t: 110_000,
+				per_a: 8_200_000,
+				per_d: 1_600_000
+			},
+		);
+		for (v, t, a, d) in [(1000, 500, 500, 200), (1500, 750, 650, 300), (2000, 1000, 800, 400)] {
+			let closed_form = coeffs.base +
+				coeffs.per_v * v as Weight +
+				coeffs.per_t * t as Weight +
+				coeffs.per_a * a as Weight +
+				coeffs.per_d * d as Weight +
+				db.reads(7) + db.writes(1);
+			assert_eq!(MockWeights::submit_unsigned(v, t, a, d), closed_form);
+		}
+	}
+
+	#[test]
+	fn submit_unsigned_checked_reports_saturation() {
+		let (weight, saturated) = Weights::submit_unsigned_checked(1000, 500, 500, 200);
+		assert!(!saturated);
+		assert_eq!(weight, MockWeights::submit_unsigned(1000, 500, 500, 200));
+
+		// Reasonable weights don't saturate, even for the largest components.
+		let (_, saturated) =
+			Weights::submit_unsigned_checked(u32::MAX, u32::MAX, u32::MAX, u32::MAX);
+		assert!(!saturated);
+
+		// But mis-scaled ones do.
+		type MisScaled = ElectionWeights<RocksDbWeight, MisScaledWeights>;
+		let (weight, saturated) = MisScaled::submit_unsigned_checked(1000, 500, 500, 200);
+		assert!(!saturated);
+		assert_eq!(weight, MisScaledWeights::submit_unsigned(1000, 500, 500, 200));
+
+		let (weight, saturated) =
+			MisScaled::submit_unsigned_checked(u32::MAX, u32::MAX, u32::MAX, u32::MAX);
+		assert!(saturated);
+		assert_eq!(weight, Weight::MAX);
+	}
+
+	#[test]
+	fn entering_emergency_phase_writes_the_phase() {
+		assert_eq!(
+			Weights::on_initialize_enter_emergency(),
+			12_000_000 + RocksDbWeight::get().writes(1)
+		);
+	}
+
+	#[test]
+	fn validate_edge_nominations_scales_with_edges() {
+		assert_eq!(Weights::validate_edge_nominations(0), 0);
+		assert_eq!(
+			Weights::validate_edge_nominations(400),
+			2 * Weights::validate_edge_nominations(200)
+		);
+		assert!(
+			Weights::validate_edge_nominations(200) <
+				MockWeights::feasibility_check(1000, 500, 500, 200)
+		);
+	}
+
+	#[test]
+	fn cleanup_replaced_solution_kills_the_stored_hash() {
+		assert_eq!(
+			Weights::cleanup_replaced_solution(),
+			PLACEHOLDER_SOLUTION_HASH_WEIGHT + RocksDbWeight::get().writes(1)
+		);
+		assert_eq!(Weights::cleanup_replaced_solution(), Weights::store_solution_hash());
+	}
+
+	#[test]
+	fn within_benchmarked_range_includes_bounds() {
+		assert!(Weights::within_benchmarked_range(1500, 750, 650, 300));
+		assert!(Weights::within_benchmarked_range(1000, 500, 500, 200));
+		assert!(Weights::within_benchmarked_range(2000, 1000, 800, 400));
+
+		assert!(!Weights::within_benchmarked_range(999, 750, 650, 300));
+		assert!(!Weights::within_benchmarked_range(1500, 1001, 650, 300));
+		assert!(!Weights::within_benchmarked_range(1500, 750, 801, 300));
+		assert!(!Weights::within_benchmarked_range(1500, 750, 650, 199));
+	}
+
+	#[test]
+	fn elect_queued_fixed_weight_is_the_zero_component_weight() {
+		let db = RocksDbWeight::get();
+
+		assert_eq!(Weights::elect_queued_coeffs().base, 15_000_000);
+		assert_eq!(Weights::elect_queued_fixed_weight(), 15_000_000 + db.reads(7) + db.writes(9));
+		assert!(MockWeights::elect_queued(500, 200) > Weights::elect_queued_fixed_weight());
+	}
+
+	#[test]
+	fn storage_accesses_of_extrinsics() {
+		assert_eq!(Weights::storage_accesses("submit"), Some((5, 3)));
+		assert_eq!(
+			Weights::storage_accesses("finalize_signed_phase_accept_solution"),
+			Some((1, 2))
+		);
+		assert_eq!(Weights::storage_accesses("elect_queued"), Some((7, 9)));
+		assert_eq!(Weights::storage_accesses("sort_winners"), None);
+
+		let db = RocksDbWeight::get();
+		let (reads, writes) = Weights::storage_accesses("elect_queued").unwrap();
+		assert_eq!(
+			Weights::elect_queued_fixed_weight(),
+			Weights::elect_queued_coeffs().base +
+				db.reads(reads as Weight) +
+				db.writes(writes as Weight),
+		);
+	}
+
+	#[test]
+	fn create_snapshot_internal_is_monotonic() {
+		let [v_low, v_high] = BenchmarkConfig::VOTERS;
+		let [t_low, t_high] = BenchmarkConfig::TARGETS;
+		let coeffs = Weights::create_snapshot_internal_coeffs();
+
+		assert!(coeffs.per_v > 0 && coeffs.per_t > 0);
+		assert!(MockWeights::create_snapshot_internal(v_low, t_low) > 0);
+		for v in (v_low..v_high).step_by(100) {
+			for t in (t_low..t_high).step_by(100) {
+				let weight = MockWeights::create_snapshot_internal(v, t);
+				assert_eq!(MockWeights::create_snapshot_internal(v + 1, t) - weight, coeffs.per_v);
+				assert_eq!(MockWeights::create_snapshot_internal(v, t + 1) - weight, coeffs.per_t);
+			}
+		}
+	}
+
+	#[test]
+	fn submit_unsigned_clamped_in_range() {
+		let (weight, clamped) = Weights::submit_unsigned_clamped(1500, 750, 650, 300);
+		assert!(!clamped);
+		assert_eq!(weight, MockWeights::submit_unsigned(1500, 750, 650, 300));
+	}
+
+	#[test]
+	fn submit_unsigned_clamped_above_range() {
+		let (weight, clamped) = Weights::submit_unsigned_clamped(5000, 750, 650, 1000);
+		assert!(clamped);
+		assert_eq!(weight, MockWeights::submit_unsigned(2000, 750, 650, 400));
+		assert!(weight < MockWeights::submit_unsigned(5000, 750, 650, 1000));
+
+		let (weight, clamped) = Weights::submit_unsigned_clamped(0, 0, 0, 0);
+		assert!(clamped);
+		assert_eq!(weight, MockWeights::submit_unsigned(1000, 500, 500, 200));
+	}
+
+	#[test]
+	fn elect_queued_checked_matches_saturating() {
+		assert_eq!(
+			Weights::elect_queued_checked(650, 300),
+			Some(MockWeights::elect_queued(650, 300))
+		);
+		assert_eq!(Weights::elect_queued_checked(0, 0), Some(Weights::elect_queued_fixed_weight()));
+
+		type MisScaled = ElectionWeights<RocksDbWeight, MisScaledWeights>;
+		assert_eq!(
+			MisScaled::elect_queued_checked(650, 300),
+			Some(MisScaledWeights::elect_queued(650, 300))
+		);
+		assert_eq!(MisScaled::elect_queued_checked(u32::MAX, u32::MAX), None);
+		assert_eq!(MisScaledWeights::elect_queued(u32::MAX, u32::MAX), Weight::MAX);
+	}
+
+	#[test]
+	fn submit_unsigned_marginal_weights_are_its_slopes() {
+		let (v, t, a, d) = (1500, 750, 650, 300);
+		let weight = MockWeights::submit_unsigned(v, t, a, d);
+
+		assert_eq!(
+			MockWeights::submit_unsigned(v + 1, t, a, d) - weight,
+			Weights::submit_unsigned_marginal_weight_per_voter(),
+		);
+		assert_eq!(
+			MockWeights::submit_unsigned(v, t + 1, a, d) - weight,
+			Weights::submit_unsigned_marginal_weight_per_target(),
+		);
+		assert_eq!(
+			MockWeights::submit_unsigned(v, t, a + 1, d) - weight,
+			Weights::submit_unsigned_marginal_weight_per_active_voter(),
+		);
+		assert_eq!(
+			MockWeights::submit_unsigned(v, t, a, d + 1) - weight,
+			Weights::submit_unsigned_marginal_weight_per_desired_target(),
+		);
+	}
+}
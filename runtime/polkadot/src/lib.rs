@@ -2250,6 +2250,44 @@ mod test {
 			If the limit is too strong, maybe consider increase the limit",
 		);
 	}
+
+	#[test]
+	fn election_weights_match_their_coeffs() {
+		use pallet_election_provider_multi_phase::WeightInfo as _;
+		type Weights = weights::pallet_election_provider_multi_phase::WeightInfo<Runtime>;
+		type ElectionWeights = runtime_common::elections::ElectionWeights<
+			<Runtime as frame_system::Config>::DbWeight,
+			Weights,
+		>;
+
+		// Storage accesses and zero component weights, as in the generated weight file.
+		let db = RocksDbWeight::get();
+		for (name, reads, writes, zero_component_weight, fixed) in [
+			("submit_unsigned", 7, 1, Weights::submit_unsigned(0, 0, 0, 0), 0),
+			("feasibility_check", 4, 0, Weights::feasibility_check(0, 0, 0, 0), 0),
+			("elect_queued", 7, 9, Weights::elect_queued(0, 0), 14_945_000),
+		] {
+			assert_eq!(ElectionWeights::storage_accesses(name), Some((reads, writes)));
+			assert_eq!(
+				zero_component_weight,
+				fixed + db.reads_writes(reads as Weight, writes as Weight),
+			);
+		}
+
+		let coeffs = ElectionWeights::submit_unsigned_coeffs();
+		assert_eq!(coeffs.base, 0);
+		let storage = db.reads_writes(7, 1);
+		for (v, t, a, d) in [(1000, 500, 500, 200), (1500, 750, 650, 300), (2000, 1000, 800, 400)] {
+			assert!(ElectionWeights::within_benchmarked_range(v, t, a, d));
+			let closed_form = coeffs.base +
+				coeffs.per_v * v as Weight +
+				coeffs.per_t * t as Weight +
+				coeffs.per_a * a as Weight +
+				coeffs.per_d * d as Weight +
+				storage;
+			assert_eq!(Weights::submit_unsigned(v, t, a, d), closed_form);
+		}
+	}
 }
 
 #[cfg(test)]